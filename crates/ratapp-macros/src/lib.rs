@@ -1,12 +1,15 @@
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
-use quote::quote;
-use syn::{Data, DataEnum, DeriveInput, Type, parse_macro_input};
+use proc_macro2::{Ident, Span};
+use quote::{quote, quote_spanned};
+use syn::{
+    Data, DataEnum, DeriveInput, Generics, LitStr, Type, Visibility, parse_macro_input, parse_quote,
+};
 
 /// Derive macro to automatically implement the [`ScreenState`](ratapp::ScreenState) trait for an
 /// enum representing the application's screens.
 ///
-/// Each variant of the enum should hold a single unnamed field of the screen type. For example:
+/// Each variant of the enum should hold a single unnamed field of the screen type, and each screen
+/// type may only appear in one variant. For example:
 ///
 /// ```ignore
 /// #[derive(ratapp::Screens)]
@@ -18,13 +21,119 @@ use syn::{Data, DataEnum, DeriveInput, Type, parse_macro_input};
 ///
 /// This macro will generate:
 ///
-/// - A `ScreenID` enum with variants corresponding to each screen.
+/// - A `ScreenID` enum with variants corresponding to each screen, with the same visibility as
+///   the derived enum.
 /// - An implementation of the `ScreenState` trait for the enum, forwarding method calls to the
 ///   active screen.
+/// - `From<&AppScreens> for ScreenID`, to get the ID of a screen, and `From<ScreenID> for
+///   AppScreens`, to create the screen with a given ID.
+///
+/// Every variant's inner type must implement [`Screen<ScreenID>`](ratapp::Screen) or
+/// [`ScreenWithState<ScreenID, S>`](ratapp::ScreenWithState). If one doesn't, the compiler will
+/// point out which screen type is missing the implementation when the enum is used to run an app.
+///
+/// Mark the screen shown when the application starts with `#[screen(default)]`, and the macro
+/// will implement [`Default`] for the enum to create it:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// enum AppScreens {
+///     #[screen(default)]
+///     Home(HomeScreen),
+///     Settings(SettingsScreen),
+/// }
+/// ```
+///
+/// Only one variant can be marked. Without the attribute, [`Default`] must be implemented
+/// manually.
+///
+/// By default, the generated implementation is generic over the application state, and is
+/// available for any state type all the screens support. If a screen only implements
+/// [`ScreenWithState`](ratapp::ScreenWithState) for a specific state, you can mark its variant
+/// with `#[screen(state_type = "...")]` to pin the implementation to that state type:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// enum AppScreens {
+///     Home(HomeScreen),
+///     #[screen(state_type = "AuthState")]
+///     Login(LoginScreen),
+/// }
+/// ```
+///
+/// The implementation is then only generated for that state type, so errors about screens not
+/// supporting the application's state point at the enum instead of the `App::run()` call.
+/// Screens implementing [`Screen`](ratapp::Screen) work with any state, so they don't need the
+/// attribute. All variants with the attribute must name the same state type.
+///
+/// The generated enum is named `ScreenID` by default, which collides when more than one enum in
+/// the same module derives `Screens`. Use `#[screen(id_name = "...")]` on the enum to rename it:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// #[screen(id_name = "SettingsScreenID")]
+/// enum SettingsScreens {
+///     General(GeneralScreen),
+///     Appearance(AppearanceScreen),
+/// }
+/// ```
+///
+/// The `ScreenID` enum is allowed to have unused variants, since screens that are only navigated
+/// to through their `ScreenState::new()` conversion never name theirs. Add `#[screen(hidden_id)]`
+/// to the enum to also hide it from the documentation, for example when it's only used
+/// internally:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// #[screen(id_name = "WizardScreenID", hidden_id)]
+/// pub enum WizardScreens {
+///     Welcome(WelcomeScreen),
+///     Finish(FinishScreen),
+/// }
+/// ```
+///
+/// The screens enum may be generic, for example over the type of the items a screen lists. The
+/// generic parameters and their bounds are forwarded to the generated implementations, while the
+/// `ScreenID` enum stays the same, since it doesn't hold the screens:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// enum AppScreens<T: Clone + Send> {
+///     #[screen(default)]
+///     List(ListScreen<T>),
+///     Details(DetailsScreen<T>),
+/// }
+/// ```
+///
+/// The `ScreenID` enum implements [`Display`](std::fmt::Display), writing the variant's name, which
+/// comes in handy for terminal titles and breadcrumbs. Use `#[screen(display = "...")]` on a
+/// variant to display it differently:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// enum AppScreens {
+///     #[screen(display = "Homepage")]
+///     Home(HomeScreen),
+///     Settings(SettingsScreen),
+/// }
+/// ```
+///
+/// Screens are named after their variant in status bars and debug overlays, unless they set
+/// `Screen::NAME`. Use `#[screen(name = "...")]` and `#[screen(description = "...")]` on a variant
+/// to override the screen's `NAME` and `DESCRIPTION`:
+///
+/// ```ignore
+/// #[derive(ratapp::Screens)]
+/// enum AppScreens {
+///     #[screen(name = "Home Screen", description = "Where everything starts")]
+///     Home(HomeScreen),
+///     Settings(SettingsScreen),
+/// }
+/// ```
 ///
 /// To learn how to implement screen state without this macro, check out the
 /// [`ScreenState`](ratapp::ScreenState) trait documentation.
-#[proc_macro_derive(Screens)]
+#[proc_macro_derive(Screens, attributes(screen))]
 pub fn screen(input: proc_macro::TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -38,12 +147,31 @@ fn screens_derive(input: &DeriveInput) -> Result<TokenStream, TokenStream> {
     let r#enum = get_enum(input)?;
     let variants = get_screens_variants(r#enum)?;
 
-    let screen_id_tokens = generate_screen_id(&variants);
-    let screen_state_impl = generate_screen_state_impl(&input.ident, &variants);
+    let state_type = get_state_type(&variants)?;
+    let ScreensOptions { id_name, hidden_id } = get_screens_options(input)?;
 
+    let screen_id_tokens = generate_screen_id(&input.vis, &id_name, hidden_id, &variants);
+    let default_impl = generate_default_impl(&input.ident, &input.generics, &variants)?;
+    let default_checks = generate_default_checks(&input.generics, &variants);
+    let from_impls = generate_from_impls(&input.ident, &input.generics, &id_name, &variants);
+    let screen_state_impl = generate_screen_state_impl(
+        &input.ident,
+        &input.generics,
+        &id_name,
+        &variants,
+        state_type,
+    );
+
+    // The checks come first so their errors are reported before the ones they explain.
     Ok(quote! {
+        #default_checks
+
         #screen_id_tokens
 
+        #default_impl
+
+        #from_impls
+
         #screen_state_impl
     }
     .into())
@@ -59,7 +187,66 @@ fn get_enum(input: &DeriveInput) -> Result<&syn::DataEnum, proc_macro::TokenStre
     }
 }
 
-fn get_screens_variants(input: &DataEnum) -> Result<Vec<(&Ident, &Type)>, proc_macro::TokenStream> {
+/// The options set on the screens enum with `#[screen(...)]`.
+struct ScreensOptions {
+    /// The name of the generated ID enum, set with `#[screen(id_name = "...")]`, or `ScreenID` if
+    /// it's not set.
+    id_name: Ident,
+    /// Whether the generated ID enum is hidden from the documentation, set with
+    /// `#[screen(hidden_id)]`.
+    hidden_id: bool,
+}
+
+/// Returns the options set on the screens enum with `#[screen(...)]`.
+fn get_screens_options(input: &DeriveInput) -> Result<ScreensOptions, proc_macro::TokenStream> {
+    let mut id_name = None;
+    let mut hidden_id = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("screen"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id_name") {
+                id_name = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+                Ok(())
+            } else if meta.path.is_ident("hidden_id") {
+                hidden_id = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported screens attribute, expected `id_name` or `hidden_id`"))
+            }
+        })
+        .map_err(|err| TokenStream::from(err.to_compile_error()))?;
+    }
+
+    Ok(ScreensOptions {
+        id_name: id_name.unwrap_or_else(|| Ident::new("ScreenID", Span::call_site())),
+        hidden_id,
+    })
+}
+
+/// A variant of the screens enum.
+struct ScreenVariant<'a> {
+    name: &'a Ident,
+    ty: &'a Type,
+    /// The state type set with `#[screen(state_type = "...")]`, if any.
+    state_type: Option<Type>,
+    /// Whether the variant is marked with `#[screen(default)]`.
+    default: bool,
+    /// The name the ID is displayed as, set with `#[screen(display = "...")]`.
+    display: Option<LitStr>,
+    /// The screen's name, set with `#[screen(name = "...")]` to override `Screen::NAME`.
+    screen_name: Option<LitStr>,
+    /// The screen's description, set with `#[screen(description = "...")]` to override
+    /// `Screen::DESCRIPTION`.
+    description: Option<LitStr>,
+}
+
+fn get_screens_variants(
+    input: &DataEnum,
+) -> Result<Vec<ScreenVariant<'_>>, proc_macro::TokenStream> {
     let mut result = Vec::new();
 
     for variant in &input.variants {
@@ -72,88 +259,555 @@ fn get_screens_variants(input: &DataEnum) -> Result<Vec<(&Ident, &Type)>, proc_m
                 }.into());
             }
         };
-        result.push((name, ty));
+
+        // A screen type repeated in several variants is most likely a copy-paste mistake.
+        if let Some(other) = result.iter().find(|other: &&ScreenVariant| {
+            let other_ty = other.ty;
+            quote!(#other_ty).to_string() == quote!(#ty).to_string()
+        }) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                format!(
+                    "`{}` is already the screen type of the `{}` variant, each screen type may only \
+                     appear in one variant",
+                    quote!(#ty),
+                    other.name
+                ),
+            )
+            .to_compile_error()
+            .into());
+        }
+
+        let mut state_type = None;
+        let mut default = false;
+        let mut display = None;
+        let mut screen_name = None;
+        let mut description = None;
+
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("screen"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("state_type") {
+                    state_type = Some(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    default = true;
+                    Ok(())
+                } else if meta.path.is_ident("display") {
+                    display = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    screen_name = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("description") {
+                    description = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unsupported screen attribute, expected `state_type`, `default`, `display`, `name` or `description`",
+                    ))
+                }
+            })
+            .map_err(|err| TokenStream::from(err.to_compile_error()))?;
+        }
+
+        result.push(ScreenVariant {
+            name,
+            ty,
+            state_type,
+            default,
+            display,
+            screen_name,
+            description,
+        });
     }
 
     Ok(result)
 }
 
-// TODO: Base `pub` on app's `Screen` enum visibility.
-fn generate_screen_id(variants: &[(&Ident, &Type)]) -> proc_macro2::TokenStream {
-    let ids = variants.iter().map(|(name, _)| name);
+/// Returns the state type set on the variants with `#[screen(state_type = "...")]`, making sure
+/// they all agree on it.
+fn get_state_type<'a>(
+    variants: &'a [ScreenVariant<'a>],
+) -> Result<Option<&'a Type>, proc_macro::TokenStream> {
+    let mut state_types = variants
+        .iter()
+        .filter_map(|variant| variant.state_type.as_ref());
+
+    let Some(state_type) = state_types.next() else {
+        return Ok(None);
+    };
+
+    for other in state_types {
+        if quote!(#other).to_string() != quote!(#state_type).to_string() {
+            return Err(syn::Error::new_spanned(
+                other,
+                "all `#[screen(state_type = \"...\")]` attributes must name the same state type",
+            )
+            .to_compile_error()
+            .into());
+        }
+    }
+
+    Ok(Some(state_type))
+}
+
+/// Generates the `ScreenID` enum, with the same visibility as the screens enum, and its
+/// `Display` implementation.
+///
+/// Unused variants are allowed, since the user didn't write the enum themselves.
+fn generate_screen_id(
+    vis: &Visibility,
+    id_name: &Ident,
+    hidden_id: bool,
+    variants: &[ScreenVariant],
+) -> proc_macro2::TokenStream {
+    let ids = variants.iter().map(|variant| variant.name);
+
+    let match_display = variants.iter().map(|ScreenVariant { name, display, .. }| {
+        let display = display
+            .clone()
+            .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+
+        quote! {
+            #id_name::#name => #display,
+        }
+    });
+
+    let doc_hidden = hidden_id.then(|| quote!(#[doc(hidden)]));
 
     quote! {
         #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-        pub enum ScreenID {
+        #[allow(unused)]
+        #doc_hidden
+        #vis enum #id_name {
             #(#ids),*
         }
+
+        impl std::fmt::Display for #id_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.pad(match self {
+                    #(#match_display)*
+                })
+            }
+        }
+    }
+}
+
+/// Generates the `Default` implementation for the variant marked with `#[screen(default)]`.
+///
+/// If no variant is marked, `Default` must be implemented manually. Since the macro can't know
+/// whether it is, it generates a check pointing at `#[screen(default)]` when it's missing instead
+/// of the less helpful error the `ScreenState` bound would cause. The check needs a concrete type,
+/// so it's skipped for generic screens enums.
+fn generate_default_impl(
+    enum_name: &Ident,
+    generics: &Generics,
+    variants: &[ScreenVariant],
+) -> Result<proc_macro2::TokenStream, proc_macro::TokenStream> {
+    let mut defaults = variants.iter().filter(|variant| variant.default);
+
+    let Some(ScreenVariant { name, ty, .. }) = defaults.next() else {
+        if !generics.params.is_empty() {
+            return Ok(quote! {});
+        }
+
+        return Ok(quote! {
+            const _: () = {
+                #[diagnostic::on_unimplemented(
+                    message = "`{Self}` doesn't implement `Default`",
+                    label = "the first screen to show is unknown",
+                    note = "mark the first screen's variant with `#[screen(default)]`, or implement `Default` for `{Self}` manually"
+                )]
+                trait ScreensDefault {}
+
+                impl<T: Default> ScreensDefault for T {}
+
+                fn assert_default<T: ScreensDefault>() {}
+
+                fn check() {
+                    assert_default::<#enum_name>();
+                }
+            };
+        });
+    };
+
+    if let Some(other) = defaults.next() {
+        return Err(syn::Error::new_spanned(
+            other.name,
+            "only one variant can be marked with `#[screen(default)]`",
+        )
+        .to_compile_error()
+        .into());
+    }
+
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: Default));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics Default for #enum_name #ty_generics #where_clause {
+            fn default() -> Self {
+                #enum_name::#name(<#ty as Default>::default())
+            }
+        }
+    })
+}
+
+/// Generates a check that every screen type implements `Default`, pointing at the variant whose
+/// type doesn't.
+///
+/// Screens are created with `Default` when they're navigated to, so the `ScreenState`
+/// implementation requires it, but a missing implementation would only be reported where the
+/// screens enum is used as a `ScreenState`. Like the `Default` check of the enum, it's skipped for
+/// generic screens enums.
+fn generate_default_checks(
+    generics: &Generics,
+    variants: &[ScreenVariant],
+) -> proc_macro2::TokenStream {
+    if !generics.params.is_empty() {
+        return quote! {};
+    }
+
+    // The checks are bounds rather than calls so they're reported along with, and before, the
+    // errors of the bounds of the generated implementations.
+    let checks = variants.iter().map(|ScreenVariant { ty, .. }| {
+        quote_spanned! {syn::spanned::Spanned::span(ty)=>
+            #ty: ScreenDefault
+        }
+    });
+
+    quote! {
+        const _: () = {
+            #[diagnostic::on_unimplemented(
+                message = "the screen type `{Self}` doesn't implement `Default`",
+                label = "screens are created with `Default` when they're navigated to",
+                note = "derive or implement `Default` for `{Self}`"
+            )]
+            trait ScreenDefault {}
+
+            impl<T: Default> ScreenDefault for T {}
+
+            struct AssertScreensDefault
+            where
+                #(#checks),*;
+        };
+    }
+}
+
+/// Generates the conversions between the screens enum and the `ScreenID` enum.
+fn generate_from_impls(
+    enum_name: &Ident,
+    generics: &Generics,
+    id_name: &Ident,
+    variants: &[ScreenVariant],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Creating a screen from its ID requires every screen type to implement `Default`.
+    let mut from_id_generics = generics.clone();
+    from_id_generics.make_where_clause().predicates.extend(
+        variants
+            .iter()
+            .map(|ScreenVariant { ty, .. }| -> syn::WherePredicate { parse_quote!(#ty: Default) }),
+    );
+    let from_id_where_clause = &from_id_generics.where_clause;
+
+    let match_from_screen = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(_) => #id_name::#name,
+        }
+    });
+
+    let match_from_id = variants.iter().map(|ScreenVariant { name, ty, .. }| {
+        quote! {
+            #id_name::#name => #enum_name::#name(<#ty as Default>::default()),
+        }
+    });
+
+    quote! {
+        impl #impl_generics From<&#enum_name #ty_generics> for #id_name #where_clause {
+            fn from(screen: &#enum_name #ty_generics) -> Self {
+                match screen {
+                    #(#match_from_screen)*
+                }
+            }
+        }
+
+        impl #impl_generics From<#id_name> for #enum_name #ty_generics #from_id_where_clause {
+            fn from(id: #id_name) -> Self {
+                match id {
+                    #(#match_from_id)*
+                }
+            }
+        }
     }
 }
 
 fn generate_screen_state_impl(
     enum_name: &Ident,
-    variants: &[(&Ident, &Type)],
+    generics: &Generics,
+    id_name: &Ident,
+    variants: &[ScreenVariant],
+    state_type: Option<&Type>,
 ) -> proc_macro2::TokenStream {
-    let where_bounds = variants.iter().map(|(_, ty)| {
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    // The implementation is generic over the state unless a variant narrows it down, and always
+    // generic over the custom event type, on top of the generic parameters of the screens enum.
+    // They're named so they don't collide with the enum's own parameters.
+    let mut impl_generics = generics.clone();
+    let state: Type = match state_type {
+        Some(state_type) => state_type.clone(),
+        None => {
+            impl_generics.params.push(parse_quote!(RatappState));
+            parse_quote!(RatappState)
+        }
+    };
+    impl_generics.params.push(parse_quote!(RatappEvent));
+
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .extend(variants.iter().map(
+        |ScreenVariant { ty, .. }| -> syn::WherePredicate {
+            parse_quote!(#ty: ratapp::ScreenWithState<#id_name, #state, RatappEvent> + Default)
+        },
+    ));
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+    let match_new = variants.iter().map(|ScreenVariant { name, ty, .. }| {
         quote! {
-            #ty : ratapp::ScreenWithState<ScreenID, S>
+            #id_name::#name => #enum_name::#name(<#ty as Default>::default()),
         }
     });
 
-    let match_new = variants.iter().map(|(name, ty)| {
+    let match_try_new = variants.iter().map(|ScreenVariant { name, ty, .. }| {
         quote! {
-            ScreenID::#name => #enum_name::#name(#ty::default()),
+            #id_name::#name => <#ty as ratapp::ScreenWithState<#id_name, #state, RatappEvent>>::try_new().map(#enum_name::#name),
         }
     });
 
-    let match_draw = variants.iter().map(|(name, _)| {
+    let match_id = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
-            #enum_name::#name(screen) => ScreenWithState::draw(screen, frame, state),
+            #enum_name::#name(_) => #id_name::#name,
         }
     });
 
-    let match_on_event = variants.iter().map(|(name, _)| {
+    // Screens without a name are named after their variant.
+    let match_name = variants.iter().map(
+        |ScreenVariant {
+             name,
+             ty,
+             screen_name,
+             ..
+         }| {
+            let screen_name = match screen_name {
+                Some(screen_name) => quote!(#screen_name),
+                None => {
+                    let variant_name = LitStr::new(&name.to_string(), name.span());
+                    quote! {
+                        match <#ty as ratapp::ScreenWithState<#id_name, #state, RatappEvent>>::NAME {
+                            "" => #variant_name,
+                            screen_name => screen_name,
+                        }
+                    }
+                }
+            };
+
+            quote! {
+                #enum_name::#name(_) => #screen_name,
+            }
+        },
+    );
+
+    let match_description = variants.iter().map(
+        |ScreenVariant {
+             name,
+             ty,
+             description,
+             ..
+         }| {
+            let description = match description {
+                Some(description) => quote!(#description),
+                None => quote! {
+                    <#ty as ratapp::ScreenWithState<#id_name, #state, RatappEvent>>::DESCRIPTION
+                },
+            };
+
+            quote! {
+                #enum_name::#name(_) => #description,
+            }
+        },
+    );
+
+    let match_preferred_area = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::preferred_area(screen, full_area, state),
+        }
+    });
+
+    let match_draw_with_context = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::draw_with_context(screen, frame, context, state),
+        }
+    });
+
+    let match_on_event = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
             #enum_name::#name(screen) => ScreenWithState::on_event(screen, event, navigator, state).await,
         }
     });
 
-    let match_on_enter = variants.iter().map(|(name, _)| {
+    let match_on_resize = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_resize(screen, cols, rows, navigator, state).await,
+        }
+    });
+
+    let match_on_focus_gained = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_focus_gained(screen, navigator, state).await,
+        }
+    });
+
+    let match_on_focus_lost = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_focus_lost(screen, navigator, state).await,
+        }
+    });
+
+    let match_on_paste = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_paste(screen, content, navigator, state).await,
+        }
+    });
+
+    let match_on_event_timeout = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_event_timeout(screen, navigator, state).await,
+        }
+    });
+
+    let match_can_leave = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::can_leave(screen, navigator, state).await,
+        }
+    });
+
+    let match_can_enter = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::can_enter(screen, navigator, state).await,
+        }
+    });
+
+    let match_title = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::title(screen, state),
+        }
+    });
+
+    let match_is_overlay = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::is_overlay(screen, state),
+        }
+    });
+
+    let match_on_custom_event = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_custom_event(screen, event, navigator, state).await,
+        }
+    });
+
+    let match_tick_interval = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::tick_interval(screen, state),
+        }
+    });
+
+    let match_on_tick = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_tick(screen, tick, navigator, state).await,
+        }
+    });
+
+    let match_help_text = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::help_text(screen, state),
+        }
+    });
+
+    let match_consumes_quit_key = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::consumes_quit_key(screen, state),
+        }
+    });
+
+    let match_filter_event = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::filter_event(screen, event, state),
+        }
+    });
+
+    let match_capture_focus = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::capture_focus(screen, state),
+        }
+    });
+
+    let match_restore_focus = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::restore_focus(screen, focus, state),
+        }
+    });
+
+    let match_on_enter = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
             #enum_name::#name(screen) => ScreenWithState::on_enter(screen, navigator, state).await,
         }
     });
 
-    let match_on_exit = variants.iter().map(|(name, _)| {
+    let match_on_exit = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
             #enum_name::#name(screen) => ScreenWithState::on_exit(screen, navigator, state).await,
         }
     });
 
-    let match_on_pause = variants.iter().map(|(name, _)| {
+    let match_on_pause = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
             #enum_name::#name(screen) => ScreenWithState::on_pause(screen, navigator, state).await,
         }
     });
 
-    let match_on_resume = variants.iter().map(|(name, _)| {
+    let match_on_resume = variants.iter().map(|ScreenVariant { name, .. }| {
+        quote! {
+            #enum_name::#name(screen) => ScreenWithState::on_resume(screen, reason, navigator, state).await,
+        }
+    });
+
+    let match_on_receive = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
-            #enum_name::#name(screen) => ScreenWithState::on_resume(screen, navigator, state).await,
+            #enum_name::#name(screen) => ScreenWithState::on_receive(screen, data, navigator, state).await,
         }
     });
 
-    let match_task = variants.iter().map(|(name, _)| {
+    let match_task = variants.iter().map(|ScreenVariant { name, .. }| {
         quote! {
             #enum_name::#name(screen) => ScreenWithState::task(screen, navigator, state).await,
         }
     });
 
     let screen_state_impl = quote! {
-        impl<S> ratapp::ScreenState<S> for #enum_name
-        where
-            #( #where_bounds, )*
+        impl #impl_generics ratapp::ScreenState<#state, RatappEvent> for #enum_name #ty_generics
+        #where_clause
         {
-            type ID = ScreenID;
+            type ID = #id_name;
 
             fn new(id: Self::ID) -> Self {
                 match id {
@@ -161,15 +815,47 @@ fn generate_screen_state_impl(
                 }
             }
 
-            fn draw(&mut self, frame: &mut ratatui::Frame, state: &S) {
+            fn try_new(id: Self::ID) -> Option<Self> {
+                match id {
+                    #(#match_try_new)*
+                }
+            }
+
+            fn id(&self) -> Self::ID {
+                match self {
+                    #(#match_id)*
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(#match_name)*
+                }
+            }
+
+            fn description(&self) -> &'static str {
+                match self {
+                    #(#match_description)*
+                }
+            }
+
+            fn preferred_area(&self, full_area: ratatui::layout::Rect, state: &#state) -> ratatui::layout::Rect {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_preferred_area)*
+                }
+            }
+
+            fn draw_with_context(&mut self, frame: &mut ratatui::Frame, context: ratapp::DrawContext<Self::ID>, state: &#state) {
                 use ratapp::ScreenWithState;
 
                 match self {
-                    #(#match_draw)*
+                    #(#match_draw_with_context)*
                 }
             }
 
-            async fn on_event(&mut self, event: ratatui::crossterm::event::Event, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_event(&mut self, event: &ratatui::crossterm::event::Event, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -177,7 +863,143 @@ fn generate_screen_state_impl(
                 }
             }
 
-            async fn on_enter(&mut self, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_resize(&mut self, cols: u16, rows: u16, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_resize)*
+                }
+            }
+
+            async fn on_focus_gained(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_focus_gained)*
+                }
+            }
+
+            async fn on_focus_lost(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_focus_lost)*
+                }
+            }
+
+            async fn on_paste(&mut self, content: String, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_paste)*
+                }
+            }
+
+            async fn on_event_timeout(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_event_timeout)*
+                }
+            }
+
+            async fn can_leave(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> bool {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_can_leave)*
+                }
+            }
+
+            async fn can_enter(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> bool {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_can_enter)*
+                }
+            }
+
+            fn title(&self, state: &#state) -> Option<String> {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_title)*
+                }
+            }
+
+            fn is_overlay(&self, state: &#state) -> bool {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_is_overlay)*
+                }
+            }
+
+            async fn on_custom_event(&mut self, event: RatappEvent, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_custom_event)*
+                }
+            }
+
+            fn tick_interval(&self, state: &#state) -> Option<std::time::Duration> {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_tick_interval)*
+                }
+            }
+
+            async fn on_tick(&mut self, tick: u64, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_tick)*
+                }
+            }
+
+            fn help_text(&self, state: &#state) -> Vec<(ratatui::crossterm::event::KeyCode, &'static str)> {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_help_text)*
+                }
+            }
+
+            fn consumes_quit_key(&self, state: &#state) -> bool {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_consumes_quit_key)*
+                }
+            }
+
+            fn filter_event(&self, event: &ratatui::crossterm::event::Event, state: &#state) -> bool {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_filter_event)*
+                }
+            }
+
+            fn capture_focus(&self, state: &#state) -> Option<ratapp::FocusState> {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_capture_focus)*
+                }
+            }
+
+            fn restore_focus(&mut self, focus: ratapp::FocusState, state: &mut #state) {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_restore_focus)*
+                }
+            }
+
+            async fn on_enter(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -185,7 +1007,7 @@ fn generate_screen_state_impl(
                 }
             }
 
-            async fn on_exit(&mut self, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_exit(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -193,7 +1015,7 @@ fn generate_screen_state_impl(
                 }
             }
 
-            async fn on_pause(&mut self, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_pause(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -201,7 +1023,7 @@ fn generate_screen_state_impl(
                 }
             }
 
-            async fn on_resume(&mut self, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_resume(&mut self, reason: ratapp::ResumeReason<Self::ID>, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -209,7 +1031,15 @@ fn generate_screen_state_impl(
                 }
             }
 
-            async fn task(&mut self, navigator: ratapp::Navigator<Self::ID>, state: &mut S) {
+            async fn on_receive(&mut self, data: Box<dyn std::any::Any + Send>, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) -> ratapp::HookResult {
+                use ratapp::ScreenWithState;
+
+                match self {
+                    #(#match_on_receive)*
+                }
+            }
+
+            async fn task(&mut self, navigator: ratapp::Navigator<Self::ID, RatappEvent>, state: &mut #state) {
                 use ratapp::ScreenWithState;
 
                 match self {
@@ -1,7 +1,7 @@
 //! The same app from examples/tutorial.rs, but using the global app state for the counter instead
 //! of screen state.
 
-use ratapp::{App, Navigator, Screen, ScreenWithState, Screens};
+use ratapp::{App, DrawContext, HookResult, Navigator, Screen, ScreenWithState, Screens};
 use ratatui::{
     Frame,
     crossterm::event::{Event, KeyCode},
@@ -23,21 +23,21 @@ struct State {
 
 #[derive(Screens)]
 enum AppScreens {
+    #[screen(default)]
     Home(HomeScreen),
     List(ListScreen),
 }
 
-impl Default for AppScreens {
-    fn default() -> Self {
-        AppScreens::Home(HomeScreen)
-    }
-}
-
 #[derive(Default)]
 struct HomeScreen;
 
 impl ScreenWithState<ScreenID, State> for HomeScreen {
-    fn draw(&mut self, frame: &mut Frame, state: &State) {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        state: &State,
+    ) {
         let text = Paragraph::new(vec![
             Line::from("Hello ratapp!"),
             Line::from(""),
@@ -53,7 +53,12 @@ impl ScreenWithState<ScreenID, State> for HomeScreen {
         frame.render_widget(text, frame.area());
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>, state: &mut State) {
+    async fn on_event(
+        &mut self,
+        event: &Event,
+        navigator: Navigator<ScreenID>,
+        state: &mut State,
+    ) -> HookResult {
         if let Event::Key(key_event) = event {
             match key_event.code {
                 KeyCode::Up => {
@@ -73,6 +78,8 @@ impl ScreenWithState<ScreenID, State> for HomeScreen {
 
             navigator.redraw();
         }
+
+        Ok(())
     }
 }
 
@@ -89,7 +96,7 @@ impl Default for ListScreen {
 }
 
 impl Screen<ScreenID> for ListScreen {
-    fn draw(&mut self, frame: &mut Frame) {
+    fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
         let layout = Layout::vertical([
             Constraint::Length(3),
             Constraint::Length(1), // Gap
@@ -117,7 +124,7 @@ impl Screen<ScreenID> for ListScreen {
         frame.render_widget(text, text_area);
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+    async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
         if let Event::Key(key_event) = event {
             match key_event.code {
                 KeyCode::Up => {
@@ -143,5 +150,7 @@ impl Screen<ScreenID> for ListScreen {
 
             navigator.redraw();
         }
+
+        Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use ratapp::{App, Navigator, Screen, Screens};
+use ratapp::{App, DrawContext, HookResult, Navigator, Screen, Screens};
 use ratatui::{
     Frame,
     crossterm::event::{Event, KeyCode},
@@ -15,16 +15,11 @@ async fn main() {
 
 #[derive(Screens)]
 enum AppScreens {
+    #[screen(default)]
     Home(TickBasedScreen),
 }
 
-impl Default for AppScreens {
-    fn default() -> Self {
-        AppScreens::Home(TickBasedScreen::default())
-    }
-}
-
-fn get_tick(tick: usize) -> char {
+fn get_tick(tick: u64) -> char {
     match tick % 4 {
         0 => '-',
         1 => '\\',
@@ -36,11 +31,11 @@ fn get_tick(tick: usize) -> char {
 
 #[derive(Default)]
 struct TickBasedScreen {
-    tick: usize,
+    tick: u64,
 }
 
 impl Screen<ScreenID> for TickBasedScreen {
-    fn draw(&mut self, frame: &mut Frame) {
+    fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
         let text = Text::from(format!(
             "{} Dummy loading... (press Q to exit)",
             get_tick(self.tick)
@@ -49,17 +44,24 @@ impl Screen<ScreenID> for TickBasedScreen {
         frame.render_widget(text, frame.area());
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+    async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
         if let Event::Key(key_event) = event
             && key_event.code == KeyCode::Char('q')
         {
             navigator.exit();
         }
+
+        Ok(())
     }
 
-    async fn task(&mut self, navigator: Navigator<ScreenID>) {
-        tokio::time::sleep(Duration::from_millis(200)).await;
-        self.tick = self.tick.wrapping_add(1);
+    fn tick_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(200))
+    }
+
+    async fn on_tick(&mut self, tick: u64, navigator: Navigator<ScreenID>) -> HookResult {
+        self.tick = tick;
         navigator.redraw();
+
+        Ok(())
     }
 }
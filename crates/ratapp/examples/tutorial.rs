@@ -1,4 +1,4 @@
-use ratapp::{App, Navigator, Screen, Screens};
+use ratapp::{App, DrawContext, HookResult, Navigator, Screen, Screens};
 use ratatui::{
     Frame,
     crossterm::event::{Event, KeyCode},
@@ -16,23 +16,18 @@ async fn main() {
 
 #[derive(Screens)]
 enum AppScreens {
+    #[screen(default)]
     Home(HomeScreen),
     List(ListScreen),
 }
 
-impl Default for AppScreens {
-    fn default() -> Self {
-        AppScreens::Home(HomeScreen::default())
-    }
-}
-
 #[derive(Default)]
 struct HomeScreen {
     counter: u32,
 }
 
 impl Screen<ScreenID> for HomeScreen {
-    fn draw(&mut self, frame: &mut Frame) {
+    fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
         let text = Paragraph::new(vec![
             Line::from("Hello ratapp!"),
             Line::from(""),
@@ -48,7 +43,7 @@ impl Screen<ScreenID> for HomeScreen {
         frame.render_widget(text, frame.area());
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+    async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
         if let Event::Key(key_event) = event {
             match key_event.code {
                 KeyCode::Up => {
@@ -66,6 +61,8 @@ impl Screen<ScreenID> for HomeScreen {
                 _ => {}
             }
         }
+
+        Ok(())
     }
 }
 
@@ -82,7 +79,7 @@ impl Default for ListScreen {
 }
 
 impl Screen<ScreenID> for ListScreen {
-    fn draw(&mut self, frame: &mut Frame) {
+    fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
         let layout = Layout::vertical([
             Constraint::Length(3),
             Constraint::Length(1), // Gap
@@ -108,7 +105,7 @@ impl Screen<ScreenID> for ListScreen {
         frame.render_widget(text, text_area);
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+    async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
         if let Event::Key(key_event) = event {
             match key_event.code {
                 KeyCode::Up => {
@@ -132,5 +129,7 @@ impl Screen<ScreenID> for ListScreen {
                 _ => {}
             }
         }
+
+        Ok(())
     }
 }
@@ -1,13 +1,57 @@
 //! The main application loop and event handling.
 
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    fmt, future, io,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    panic,
+    pin::{Pin, pin},
+    sync::{Mutex, Once},
+    time::Duration,
+};
+
+use ratatui::{
+    DefaultTerminal, Frame, Terminal, TerminalOptions, Viewport,
+    backend::{Backend, CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    crossterm::{
+        event::{
+            self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+            EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, KeyCode,
+            KeyEventKind, KeyModifiers,
+        },
+        execute,
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
+    },
+    layout::{Position, Rect},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{self, Instant, MissedTickBehavior},
+};
 
-use ratatui::crossterm::event::{self, Event};
-use tokio::sync::mpsc;
+#[cfg(feature = "persistence")]
+use std::path::Path;
 
+#[cfg(feature = "persistence")]
+use serde::{Serialize, de::DeserializeOwned};
+
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+
+#[cfg(feature = "persistence")]
+use crate::persistence::{self, StateSnapshot};
 use crate::{
-    navigation::{Action, Navigator},
-    screen::ScreenState,
+    builder::AppBuilder,
+    event_source::EventSource,
+    help::HelpScreen,
+    navigation::{
+        Action, Command, ManagedTasks, Navigator, NavigatorMiddleware, PeekableNavigator,
+        ScreenData,
+    },
+    screen::{DrawContext, HookError, HookResult, ResumeReason, ScreenState},
 };
 
 /// The main application struct that runs the event loop and manages screens.
@@ -39,180 +83,3073 @@ use crate::{
 /// [`ScreenWithState`](crate::ScreenWithState) trait for your screens instead of the
 /// [`Screen`](crate::Screen) trait. This allows your screens to access and modify the shared
 /// application state.
-pub struct App<T = ()> {
-    events: mpsc::UnboundedReceiver<Event>,
+///
+/// For further configuration, like enabling mouse support or limiting the frame rate, use an
+/// [`AppBuilder`].
+///
+/// The `E` parameter is the type of the custom events screens receive in their `on_custom_event`
+/// hook, sent with [`Navigator::send_event()`]. Set it with [`App::with_custom_events()`].
+///
+/// The `H` parameter holds the handlers taking screen IDs, like the error handler or the
+/// middlewares. They can only be set once the screens type is, with [`App::with_screens()`], so
+/// that they're checked against it at compile time. Check out [`Handlers`] for more information.
+pub struct App<T = (), E = (), H = NoHandlers> {
+    events: Option<mpsc::UnboundedReceiver<Event>>,
     state: T,
+    config: Config,
+    #[cfg(feature = "persistence")]
+    snapshot: Option<StateSnapshot<T>>,
+    /// Creates the state the application is reset to by [`Navigator::restart()`], if it should be.
+    restart_state: Option<RestartState<T>>,
+    handlers: H,
+    custom_events: PhantomData<fn(E)>,
 }
 
-impl App<()> {
-    /// Creates a new `App` instance with the default screen without any application state.
+/// The configuration of an [`App`], set through [`AppBuilder`] or the `App::with_*()` methods.
+pub(crate) struct Config {
+    pub(crate) draw_error_policy: DrawErrorPolicy,
+    pub(crate) event_priority: EventPriority,
+    pub(crate) max_stack_depth: Option<usize>,
+    pub(crate) stack_overflow_policy: StackOverflowPolicy,
+    pub(crate) shutdown_timeout: Option<Duration>,
+    pub(crate) pause_on_focus_loss: bool,
+    pub(crate) frame_rate: Option<f64>,
+    pub(crate) mouse: bool,
+    pub(crate) focus_events: bool,
+    pub(crate) bracketed_paste: bool,
+    pub(crate) alternate_screen: bool,
+    pub(crate) raw_mode: bool,
+    pub(crate) title: Option<String>,
+    pub(crate) help_key: KeyCode,
+    pub(crate) quit_keys: Vec<KeyCode>,
+    pub(crate) event_timeout: Option<Duration>,
+    pub(crate) double_buffer: bool,
+    #[cfg(feature = "tracing")]
+    pub(crate) slow_hook_threshold: Duration,
+    /// The span of the screen transition being processed, which the hooks it calls are timed in,
+    /// or a disabled span between transitions.
+    #[cfg(feature = "tracing")]
+    pub(crate) transition_span: tracing::Span,
+    /// Starts reading the [`EventSource`] set with [`App::with_event_source()`], if any.
+    pub(crate) event_source: Option<EventSourceStarter>,
+    /// The [`MetricsReporter`] set with [`App::with_metrics_reporter()`], along with how many
+    /// frames to draw between reports.
+    pub(crate) metrics_reporter: Option<(u64, MetricsReporter)>,
+}
+
+/// A function deciding what to do with the errors returned by screen lifecycle hooks.
+pub(crate) type ErrorHandler<ID, E> =
+    Box<dyn Fn(&HookError, Navigator<ID, E>) -> HookErrorAction + Send + Sync>;
+
+/// A function called with the ID of a screen that couldn't be created to navigate to it.
+pub(crate) type NavigationErrorHandler<ID, E> = Box<dyn Fn(ID, Navigator<ID, E>) + Send + Sync>;
+
+/// A function handling events before they reach the current screen, returning whether it consumed
+/// them.
+pub(crate) type GlobalEventHandler<ID, E> =
+    Box<dyn Fn(Event, Navigator<ID, E>) -> bool + Send + Sync>;
+
+/// A [`NavigatorMiddleware`] intercepting navigation actions before they're processed.
+pub(crate) type Middleware<ID> = Box<dyn NavigatorMiddleware<ID> + Send + Sync>;
+
+/// A function spawning the task reading an [`EventSource`], returning the receiver of its events.
+pub(crate) type EventSourceStarter =
+    Box<dyn FnOnce() -> mpsc::UnboundedReceiver<Event> + Send + Sync>;
+
+/// A function creating a screen asynchronously, instead of with [`ScreenState::new()`].
+pub(crate) type ScreenFactory<S> = Box<dyn Fn() -> Pin<Box<dyn Future<Output = S>>> + Send + Sync>;
+
+/// A function receiving the [`RenderMetrics`] of the application every few frames.
+pub(crate) type MetricsReporter = Box<dyn Fn(RenderMetrics) + Send + Sync>;
+
+/// A function creating the application state to reset to when the application is restarted.
+pub(crate) type RestartState<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// The handlers of an [`App`] whose screens type isn't set yet.
+///
+/// Handlers taking screen IDs, like the error handler or the middlewares, can only be set once it
+/// is, with [`App::with_screens()`] or [`AppBuilder::screens()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoHandlers;
+
+/// The handlers of an [`App`] run with the screens type `S`, whose screen ID type is `ID`.
+///
+/// They're set with the `App::with_*()` methods available once the screens type is set with
+/// [`App::with_screens()`] or [`AppBuilder::screens()`], so handlers taking another screen ID
+/// type, or screen factories returning another screens type, fail to compile.
+pub struct Handlers<S, ID, E = ()> {
+    pub(crate) error_handler: Option<ErrorHandler<ID, E>>,
+    pub(crate) navigation_error_handler: Option<NavigationErrorHandler<ID, E>>,
+    /// In the order they were added.
+    pub(crate) event_handlers: Vec<GlobalEventHandler<ID, E>>,
+    /// In the order they were added.
+    pub(crate) middlewares: Vec<Middleware<ID>>,
+    /// Along with the ID of the screen they create.
+    pub(crate) screen_factories: Vec<(ID, ScreenFactory<S>)>,
+}
+
+/// The handlers an [`App`] can be run with for the screens type `S`.
+///
+/// It's implemented by [`NoHandlers`], for any screens type, and by the [`Handlers`] set for `S`
+/// only, so an application can't be run with other screens than the ones its handlers were set
+/// for. It's sealed, so it can't be implemented outside of ratapp.
+pub trait HandlersFor<S, T, E>: sealed::Sealed
+where
+    S: ScreenState<T, E>,
+{
+    /// Takes the handlers out for a run, until they're put back with [`HandlersFor::restore()`].
+    #[doc(hidden)]
+    fn take(&mut self) -> Handlers<S, S::ID, E>;
+
+    /// Puts back the handlers taken by [`HandlersFor::take()`] once the run is over.
+    #[doc(hidden)]
+    fn restore(&mut self, handlers: Handlers<S, S::ID, E>);
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::NoHandlers {}
+
+    impl<S, ID, E> Sealed for super::Handlers<S, ID, E> {}
+}
+
+impl<S, T, E> HandlersFor<S, T, E> for NoHandlers
+where
+    S: ScreenState<T, E>,
+{
+    fn take(&mut self) -> Handlers<S, S::ID, E> {
+        Handlers::default()
+    }
+
+    fn restore(&mut self, _handlers: Handlers<S, S::ID, E>) {}
+}
+
+impl<S, T, E> HandlersFor<S, T, E> for Handlers<S, S::ID, E>
+where
+    S: ScreenState<T, E>,
+{
+    fn take(&mut self) -> Handlers<S, S::ID, E> {
+        mem::take(self)
+    }
+
+    fn restore(&mut self, handlers: Handlers<S, S::ID, E>) {
+        *self = handlers;
+    }
+}
+
+impl<S, ID, E> Handlers<S, ID, E> {
+    /// Passes an event to the global event handlers, returning whether one of them consumed it.
+    fn handle_global_event(&self, event: &Event, navigator: &Navigator<ID, E>) -> bool {
+        self.event_handlers
+            .iter()
+            .any(|handler| handler(event.clone(), navigator.clone()))
+    }
+
+    /// Passes an action through the middlewares, returning the action to process, if any.
+    fn intercept(&mut self, action: Action<ID>) -> Option<Action<ID>> {
+        self.middlewares
+            .iter_mut()
+            .try_fold(action, |action, middleware| middleware.intercept(&action))
+    }
+
+    /// Passes the error returned by a screen lifecycle hook, if any, to the error handler.
     ///
-    /// Returns:
-    /// [`App`] - A new application instance.
-    pub fn new() -> Self {
-        let (events_tx, events_rx) = mpsc::unbounded_channel();
+    /// Returns the error when it should be propagated out of [`App::run()`].
+    fn handle_hook_result(
+        &self,
+        result: HookResult,
+        navigator: &Navigator<ID, E>,
+    ) -> io::Result<()> {
+        let Err(err) = result else {
+            return Ok(());
+        };
 
-        tokio::task::spawn_blocking(move || {
-            loop {
-                if let Ok(event) = event::read()
-                    && events_tx.send(event).is_err()
-                {
-                    break;
-                }
-            }
-        });
+        let action = match &self.error_handler {
+            Some(handler) => handler(&err, navigator.clone()),
+            None => HookErrorAction::default(),
+        };
 
-        Self {
-            events: events_rx,
-            state: (),
+        match action {
+            HookErrorAction::Propagate => Err(io::Error::other(err)),
+            HookErrorAction::Continue => Ok(()),
         }
     }
 }
 
-impl<T> App<T> {
-    /// Creates a new `App` instance with the default screen and provided application state.
+impl<S, ID, E> Default for Handlers<S, ID, E> {
+    fn default() -> Self {
+        Handlers {
+            error_handler: None,
+            navigation_error_handler: None,
+            event_handlers: Vec::new(),
+            middlewares: Vec::new(),
+            screen_factories: Vec::new(),
+        }
+    }
+}
+
+impl<S, ID, E> fmt::Debug for Handlers<S, ID, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handlers")
+            .field("error_handler", &self.error_handler.is_some())
+            .field(
+                "navigation_error_handler",
+                &self.navigation_error_handler.is_some(),
+            )
+            .field("event_handlers", &self.event_handlers.len())
+            .field("middlewares", &self.middlewares.len())
+            .field("screen_factories", &self.screen_factories.len())
+            .finish()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            draw_error_policy: DrawErrorPolicy::default(),
+            event_priority: EventPriority::default(),
+            max_stack_depth: None,
+            stack_overflow_policy: StackOverflowPolicy::default(),
+            shutdown_timeout: None,
+            pause_on_focus_loss: false,
+            frame_rate: None,
+            mouse: false,
+            focus_events: false,
+            bracketed_paste: false,
+            alternate_screen: true,
+            raw_mode: true,
+            title: None,
+            help_key: KeyCode::Char('?'),
+            quit_keys: Vec::new(),
+            event_timeout: None,
+            double_buffer: false,
+            #[cfg(feature = "tracing")]
+            slow_hook_threshold: Duration::from_millis(100),
+            #[cfg(feature = "tracing")]
+            transition_span: tracing::Span::none(),
+            event_source: None,
+            metrics_reporter: None,
+        }
+    }
+}
+
+impl Config {
+    /// Sets up the terminal according to the configuration.
+    fn init_terminal(&self) -> io::Result<DefaultTerminal> {
+        // Restores the terminal if the application panics, like `ratatui::init()` would.
+        install_panic_hook();
+
+        if self.raw_mode {
+            terminal::enable_raw_mode()?;
+        }
+
+        if self.alternate_screen {
+            execute!(io::stdout(), EnterAlternateScreen)?;
+        }
+
+        let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+        if self.mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+
+        if self.focus_events {
+            execute!(io::stdout(), EnableFocusChange)?;
+        }
+
+        if self.bracketed_paste {
+            execute!(io::stdout(), EnableBracketedPaste)?;
+        }
+
+        if let Some(title) = &self.title {
+            execute!(io::stdout(), SetTitle(title))?;
+        }
+
+        Ok(terminal)
+    }
+
+    /// Restores the terminal to its original state, undoing [`Config::init_terminal()`].
+    fn restore_terminal(&self) {
+        if self.mouse {
+            let _ = execute!(io::stdout(), DisableMouseCapture);
+        }
+
+        if self.focus_events {
+            let _ = execute!(io::stdout(), DisableFocusChange);
+        }
+
+        if self.bracketed_paste {
+            let _ = execute!(io::stdout(), DisableBracketedPaste);
+        }
+
+        if self.alternate_screen {
+            let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        }
+
+        if self.raw_mode {
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    /// The minimum time between two draws, if the frame rate is limited.
+    fn frame_interval(&self) -> Option<Duration> {
+        self.frame_rate
+            .filter(|fps| fps.is_finite() && *fps > 0.0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps))
+    }
+
+    /// Applies the stack overflow policy to a navigation that would leave `depth` screens in the
+    /// stack, `id` being the screen navigated to.
     ///
-    /// Parameters:
-    /// * `state` - The initial application state.
+    /// Returns how many of the oldest screens above the root have to exit to make room, or `None`
+    /// when the navigation has to be dropped.
+    fn make_room(&self, depth: usize, id: impl fmt::Debug) -> Option<usize> {
+        let Some(max) = self.max_stack_depth.filter(|max| depth > *max) else {
+            return Some(0);
+        };
+
+        match self.stack_overflow_policy {
+            // The root screen is never dropped to make room.
+            StackOverflowPolicy::DropOldest if max > 1 => Some(depth - max),
+            StackOverflowPolicy::DropOldest | StackOverflowPolicy::DropNewest => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    screen = ?id,
+                    "The navigation stack is full, dropping the navigation"
+                );
+                #[cfg(not(feature = "tracing"))]
+                let _ = id;
+
+                None
+            }
+            StackOverflowPolicy::Panic => {
+                panic!("The navigation stack is full, can't navigate to screen {id:?}")
+            }
+        }
+    }
+
+    /// Awaits a screen lifecycle hook, logging how long it took when the `tracing` feature is
+    /// enabled.
     ///
-    /// Returns:
-    /// [`App`] - A new application instance.
-    pub fn with_state(state: T) -> Self {
-        let (events_tx, events_rx) = mpsc::unbounded_channel();
+    /// Hooks slower than `slow_hook_threshold` are logged as warnings, and the rest as debug
+    /// messages. Hooks called while processing a screen transition are logged in its span.
+    async fn time_hook<F: Future>(&self, hook: &str, id: impl fmt::Debug, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            let start = Instant::now();
+            let output = future.instrument(self.transition_span.clone()).await;
+            let elapsed = start.elapsed();
+            let duration_us = elapsed.as_micros();
 
-        tokio::task::spawn_blocking(move || {
-            loop {
-                if let Ok(event) = event::read()
-                    && events_tx.send(event).is_err()
-                {
-                    break;
+            self.transition_span.in_scope(|| {
+                if elapsed > self.slow_hook_threshold {
+                    tracing::warn!(%duration_us, hook, "Screen {id:?} {hook} took {elapsed:?}");
+                } else {
+                    tracing::debug!(%duration_us, hook, "Screen {id:?} {hook} took {elapsed:?}");
                 }
-            }
-        });
+            });
 
-        Self {
-            events: events_rx,
-            state,
+            output
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (hook, id);
+            future.await
         }
     }
 
-    /// Runs the main application loop, handling events and screen re-drawing.
+    /// Awaits a screen lifecycle hook like [`Config::time_hook()`], handling the error it may
+    /// return with [`Handlers::handle_hook_result()`].
     ///
-    /// Returns:
-    /// `std::io::Result<()>` - Result of the application run.
-    pub async fn run<S>(&mut self) -> std::io::Result<()>
+    /// With the `tracing` feature, screens being entered and exited are also logged at the `INFO`
+    /// level.
+    async fn call_hook<S, ID, E>(
+        &self,
+        handlers: &Handlers<S, ID, E>,
+        hook: &str,
+        id: ID,
+        navigator: &Navigator<ID, E>,
+        future: impl Future<Output = HookResult>,
+    ) -> io::Result<()>
     where
-        S: ScreenState<T>,
+        ID: fmt::Debug + 'static,
     {
-        let mut terminal = ratatui::init();
+        let result = self.time_hook(hook, &id, future).await;
 
-        let mut screens = VecDeque::from([S::default()]);
+        #[cfg(feature = "tracing")]
+        self.transition_span.in_scope(|| match hook {
+            "on_enter" => tracing::info!(screen = ?id, "screen entered"),
+            "on_exit" => tracing::info!(screen = ?id, "screen exited"),
+            _ => {}
+        });
 
-        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
-        let navigator = Navigator::new(events_tx);
+        handlers.handle_hook_result(result, navigator)
+    }
+}
 
-        screens
-            .back_mut()
-            .unwrap()
-            .on_enter(navigator.clone(), &mut self.state)
-            .await;
+/// What to do when drawing a screen to the terminal fails.
+///
+/// Set it with [`App::with_draw_error_policy()`] or [`AppBuilder::draw_error_policy()`]. Defaults to [`DrawErrorPolicy::Propagate`].
+///
+/// Retrying is appropriate when the error is likely transient, such as a terminal that is
+/// momentarily unable to accept writes. If the terminal is gone for good (e.g. the SSH session was
+/// closed or stdout was redirected to a closed pipe), retrying only delays the inevitable, so
+/// prefer propagating the error immediately in those environments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DrawErrorPolicy {
+    /// Restore the terminal and return the error from [`App::run()`] right away.
+    #[default]
+    Propagate,
 
-        let mut draw = true;
+    /// Retry drawing up to N times, waiting a short delay between attempts, before propagating the
+    /// error.
+    RetryN(usize),
+}
 
-        loop {
-            let screen = screens.back_mut().expect("No screen in the stack!");
+/// What to do when a screen is pushed onto a navigation stack that's already at its maximum depth.
+///
+/// Going forward with [`Navigator::forward()`] counts as a push, and the policy also applies to
+/// [`Navigator::replace_history()`] with more screens than the maximum depth.
+///
+/// Set the maximum depth with [`App::with_max_stack_depth()`] or
+/// [`AppBuilder::max_stack_depth()`], and the policy with [`App::with_stack_overflow_policy()`] or
+/// [`AppBuilder::stack_overflow_policy()`]. Defaults to [`StackOverflowPolicy::DropOldest`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum StackOverflowPolicy {
+    /// Exit the oldest screen above the root to make room for the new one, like a browser
+    /// forgetting its oldest history entries. The root screen is never dropped, so the push is
+    /// dropped instead if it's the only screen. A replaced history leaves out its oldest screens
+    /// above the root.
+    #[default]
+    DropOldest,
 
-            if draw {
-                terminal
-                    .draw(|frame| screen.draw(frame, &self.state))
-                    .inspect_err(|_| {
-                        ratatui::restore();
-                    })?;
+    /// Drop the push or the replaced history, leaving the stack as it is. A warning is logged with
+    /// the `tracing` feature.
+    DropNewest,
 
-                draw = false;
-            }
+    /// Panic, for applications where a full stack can only be a bug.
+    Panic,
+}
 
-            tokio::select! {
-                _ = screen.task(navigator.clone(), &mut self.state) => {},
-                Some(event) = self.events.recv() => {
-                    if let Event::Resize(_, _) = event {
-                        draw = true;
-                    }
+/// Whether terminal events or navigation actions are handled first when both are waiting.
+///
+/// Set it with [`App::with_event_priority()`] or [`AppBuilder::event_priority()`]. Defaults to
+/// [`EventPriority::Events`]. Custom events sent with [`Navigator::send_event()`] count as events.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum EventPriority {
+    /// Handle waiting events first, so the UI responds as fast as possible. A flood of events
+    /// delays the navigation actions queued meanwhile.
+    #[default]
+    Events,
 
-                    screen.on_event(event, navigator.clone(), &mut self.state).await;
-                },
-                Some(action) = events_rx.recv() => {
-                    match action {
-                        Action::Push(id) => {
-                            screen.on_pause(navigator.clone(), &mut self.state).await;
+    /// Process queued navigation actions first, so the screens handling the next events are the
+    /// ones navigated to. Useful for wizard flows that navigate right after each key press.
+    Actions,
 
-                            let mut screen = S::new(id);
-                            screen.on_enter(navigator.clone(), &mut self.state).await;
+    /// Alternate between events and navigation actions while both are waiting: after an event,
+    /// the next queued action is handled before another event, and the other way around. The
+    /// order is deterministic, so neither can delay the other by more than one turn.
+    Alternate,
+}
 
-                            screens.push_back(screen);
+/// What to do when a screen lifecycle hook returns an error.
+///
+/// It's returned by the error handler set with [`App::with_error_handler()`] or
+/// [`AppBuilder::error_handler()`]. Without an error handler, errors are always propagated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum HookErrorAction {
+    /// Restore the terminal and return the error from [`App::run()`] right away.
+    #[default]
+    Propagate,
 
-                            draw = true;
-                        }
-                        Action::Replace(id) => {
-                            let mut old_screen = screens.pop_back().unwrap();
-                            old_screen.on_exit(navigator.clone(), &mut self.state).await;
+    /// Ignore the error and keep running. The error handler may have logged it or pushed an
+    /// error screen with the navigator it was given.
+    Continue,
+}
 
-                            let mut new_screen = S::new(id);
-                            new_screen.on_enter(navigator.clone(), &mut self.state).await;
-                            screens.push_back(new_screen);
+/// Statistics about the frames the application has drawn.
+///
+/// They're reported to the function set with [`App::with_metrics_reporter()`], and screens can get
+/// the latest ones with [`Navigator::render_metrics()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct RenderMetrics {
+    /// How many frames have been drawn since the application started.
+    pub frame_count: u64,
+    /// How long drawing the last frame to the terminal took.
+    pub last_frame_duration: Duration,
+    /// How long drawing a frame to the terminal took on average.
+    pub avg_frame_duration: Duration,
+}
 
-                            draw = true;
-                        }
-                        Action::Back => {
-                            if screens.len() > 1 {
-                                let mut old_screen = screens.pop_back().unwrap();
-                                old_screen.on_exit(navigator.clone(), &mut self.state).await;
+impl RenderMetrics {
+    /// Accounts for a frame that took `duration` to draw.
+    fn record(&mut self, duration: Duration) {
+        self.frame_count += 1;
+        self.last_frame_duration = duration;
 
-                                let current_screen = screens.back_mut().unwrap();
-                                current_screen.on_resume(navigator.clone(), &mut self.state).await;
+        let previous = (self.frame_count - 1) as f64 / self.frame_count as f64;
+        self.avg_frame_duration =
+            self.avg_frame_duration.mul_f64(previous) + duration.div_f64(self.frame_count as f64);
+    }
+}
 
-                                draw = true;
-                            }
-                        }
-                        Action::Clear => {
-                            let current_screen = screens.pop_back().unwrap();
+/// The time to wait between draw attempts when using [`DrawErrorPolicy::RetryN`].
+const DRAW_RETRY_DELAY: Duration = Duration::from_millis(50);
 
-                            while let Some(mut old_screen) = screens.pop_back() {
-                                old_screen.on_exit(navigator.clone(), &mut self.state).await;
-                            }
+impl App<()> {
+    /// Creates a new `App` instance with the default screen without any application state.
+    ///
+    /// It's a shorthand for `AppBuilder::new().build()`. Check out [`AppBuilder`] for all the
+    /// available configuration options.
+    ///
+    /// Returns:
+    /// [`App`] - A new application instance.
+    pub fn new() -> Self {
+        AppBuilder::new().build()
+    }
+}
 
-                            screens.push_back(current_screen);
-                        }
-                        Action::Restart => {
-                            while let Some(mut old_screen) = screens.pop_back() {
-                                old_screen.on_exit(navigator.clone(), &mut self.state).await;
-                            }
+impl<T> App<T> {
+    /// Creates a new `App` instance with the default screen and provided application state.
+    ///
+    /// It's a shorthand for `AppBuilder::new().state(state).build()`. Check out [`AppBuilder`]
+    /// for all the available configuration options.
+    ///
+    /// Parameters:
+    /// * `state` - The initial application state.
+    ///
+    /// Returns:
+    /// [`App`] - A new application instance.
+    pub fn with_state(state: T) -> Self {
+        AppBuilder::new().state(state).build()
+    }
+
+    /// Creates a new `App` instance with the default screen and the application state returned by
+    /// an async function.
+    ///
+    /// Useful for state that needs async initialization, like loading a configuration file or
+    /// connecting to a database:
+    ///
+    /// ```ignore
+    /// let mut app = App::with_async_state(|| async {
+    ///     let db = Database::connect("sqlite://app.db").await;
+    ///     MyState { db }
+    /// })
+    /// .await;
+    ///
+    /// app.run::<AppScreens>().await?;
+    /// ```
+    ///
+    /// Parameters:
+    /// * `init` - The function initializing the application state.
+    ///
+    /// Returns:
+    /// [`App`] - A new application instance, once the state is initialized.
+    pub async fn with_async_state<F, Fut>(init: F) -> Self
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        App::with_state(init().await)
+    }
 
-                            let mut new_screen = S::default();
-                            new_screen.on_enter(navigator.clone(), &mut self.state).await;
-                            screens.push_back(new_screen);
+    /// Creates a new `App` instance with the application state saved at `path` by
+    /// [`App::with_state_snapshot()`].
+    ///
+    /// If the file doesn't exist or can't be deserialized, the default state is used instead.
+    ///
+    /// Only available with the `persistence` feature.
+    ///
+    /// Parameters:
+    /// * `path` - The file the state was saved to.
+    ///
+    /// Returns:
+    /// [`App`] - A new application instance.
+    #[cfg(feature = "persistence")]
+    pub fn with_state_from_snapshot(path: impl AsRef<Path>) -> Self
+    where
+        T: DeserializeOwned + Default,
+    {
+        App::with_state(persistence::load(path.as_ref()).unwrap_or_default())
+    }
+}
 
-                            draw = true;
-                        }
-                        Action::Exit => {
-                            while let Some(mut old_screen) = screens.pop_back() {
-                                old_screen.on_exit(navigator.clone(), &mut self.state).await;
-                            }
+impl<T, E> App<T, E> {
+    /// Sets the type of the custom events sent with [`Navigator::send_event()`].
+    ///
+    /// Screens receive them in their `on_custom_event` hook, and must implement `Screen<ID, F>` or
+    /// `ScreenWithState<ID, T, F>` to do so. Their hooks get a `Navigator<ID, F>`, which only
+    /// sends events of type `F`, so sending another type fails to compile. Useful for background
+    /// tasks to report domain-specific happenings, like a lost connection, without going through
+    /// the application state:
+    ///
+    /// ```ignore
+    /// enum NetworkEvent {
+    ///     Connected,
+    ///     Disconnected,
+    /// }
+    ///
+    /// let mut app = App::new().with_custom_events::<NetworkEvent>();
+    /// ```
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the custom event type set.
+    pub fn with_custom_events<F>(self) -> App<T, F> {
+        App {
+            events: self.events,
+            state: self.state,
+            config: self.config,
+            #[cfg(feature = "persistence")]
+            snapshot: self.snapshot,
+            restart_state: self.restart_state,
+            handlers: NoHandlers,
+            custom_events: PhantomData,
+        }
+    }
 
-                            break;
-                        }
-                        Action::Redraw => {
-                            draw = true;
-                        }
-                    }
-                }
-            }
+    /// Sets the screens type the application is run with.
+    ///
+    /// It's required to set the handlers taking screen IDs, like the error handler or the
+    /// middlewares, and the screen factories, which are then checked against the screens type at
+    /// compile time. The custom event type must be set before, with
+    /// [`App::with_custom_events()`]:
+    ///
+    /// ```ignore
+    /// let mut app = App::new()
+    ///     .with_screens::<AppScreens>()
+    ///     .with_navigation_error_handler(|id, navigator| navigator.push(ScreenID::NotFound));
+    ///
+    /// app.run().await?;
+    /// ```
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the screens type set.
+    pub fn with_screens<S>(self) -> App<T, E, Handlers<S, S::ID, E>>
+    where
+        S: ScreenState<T, E>,
+    {
+        App {
+            events: self.events,
+            state: self.state,
+            config: self.config,
+            #[cfg(feature = "persistence")]
+            snapshot: self.snapshot,
+            restart_state: self.restart_state,
+            handlers: Handlers::default(),
+            custom_events: PhantomData,
+        }
+    }
+}
+
+impl<T, E, H> App<T, E, H> {
+    /// Creates a new `App` instance from its state, configuration and handlers.
+    pub(crate) fn from_config(state: T, config: Config, handlers: H) -> Self {
+        Self {
+            events: None,
+            state,
+            config,
+            #[cfg(feature = "persistence")]
+            snapshot: None,
+            restart_state: None,
+            handlers,
+            custom_events: PhantomData,
         }
+    }
 
-        ratatui::restore();
+    /// Sets what to do when drawing a screen to the terminal fails.
+    ///
+    /// Check out [`DrawErrorPolicy`] for the available policies.
+    ///
+    /// Parameters:
+    /// * `policy` - The policy to apply on draw errors.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the policy set.
+    pub fn with_draw_error_policy(mut self, policy: DrawErrorPolicy) -> Self {
+        self.config.draw_error_policy = policy;
+        self
+    }
 
-        Ok(())
+    /// Sets whether terminal events or navigation actions are handled first when both are
+    /// waiting.
+    ///
+    /// Check out [`EventPriority`] for the available priorities.
+    ///
+    /// Parameters:
+    /// * `priority` - What to handle first.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the priority set.
+    pub fn with_event_priority(mut self, priority: EventPriority) -> Self {
+        self.config.event_priority = priority;
+        self
     }
+
+    /// Sets the maximum number of screens in the navigation stack.
+    ///
+    /// Useful for applications where the user can keep pushing screens, like deeply nested
+    /// menus, so the stack doesn't grow indefinitely. What happens to pushes onto a full stack is
+    /// set with [`App::with_stack_overflow_policy()`]. Pushes, going forward and replacing the
+    /// history with [`Navigator::replace_history()`] are limited, while the rest of the navigation
+    /// actions never grow the stack. There's no limit by default.
+    ///
+    /// Parameters:
+    /// * `depth` - The maximum number of screens in the stack, including the root screen.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the maximum depth set.
+    pub fn with_max_stack_depth(mut self, depth: usize) -> Self {
+        self.config.max_stack_depth = Some(depth);
+        self
+    }
+
+    /// Sets what to do when a screen is pushed onto a full navigation stack.
+    ///
+    /// Check out [`StackOverflowPolicy`] for the available policies, and
+    /// [`App::with_max_stack_depth()`] to set the maximum depth.
+    ///
+    /// Parameters:
+    /// * `policy` - What to do with pushes onto a full stack.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the policy set.
+    pub fn with_stack_overflow_policy(mut self, policy: StackOverflowPolicy) -> Self {
+        self.config.stack_overflow_policy = policy;
+        self
+    }
+
+    /// Sets the maximum time each screen's `on_exit` hook may take when the application exits.
+    ///
+    /// If a screen's `on_exit` hook takes longer than the timeout, it's cancelled and the next
+    /// screen's `on_exit` hook is called. This prevents a buggy hook from hanging the application
+    /// on shutdown. A warning naming the screen is logged with the `tracing` feature. By default,
+    /// there's no timeout.
+    ///
+    /// Parameters:
+    /// * `timeout` - The maximum time each `on_exit` hook may take on shutdown.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the shutdown timeout set.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.config.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether mouse events are captured.
+    ///
+    /// When enabled, mouse capture is turned on when the application starts and off when it
+    /// exits, and [`Event::Mouse`] events are delivered to screens through their `on_event` hook.
+    /// Disabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to capture mouse events.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_mouse_support(mut self, enabled: bool) -> Self {
+        self.config.mouse = enabled;
+        self
+    }
+
+    /// Sets whether the application is drawn on the terminal's alternate screen.
+    ///
+    /// The alternate screen keeps the terminal's content intact while the application runs, and
+    /// restores it on exit. Without it, the application is drawn over the terminal's main buffer,
+    /// where it's left once it exits. Enabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to use the alternate screen.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_alternate_screen(mut self, enabled: bool) -> Self {
+        self.config.alternate_screen = enabled;
+        self
+    }
+
+    /// Sets whether the terminal is put in raw mode while the application runs.
+    ///
+    /// In raw mode, key presses are delivered as they happen instead of line by line, and aren't
+    /// echoed or interpreted by the terminal, so Ctrl+C reaches screens as a key event instead of
+    /// interrupting the process. Disable it only if you know what you're doing. Enabled by
+    /// default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to enable raw mode.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_raw_mode(mut self, enabled: bool) -> Self {
+        self.config.raw_mode = enabled;
+        self
+    }
+
+    /// Sets whether the terminal reports focus changes.
+    ///
+    /// When enabled, screens' `on_focus_gained` and `on_focus_lost` hooks are called as the
+    /// terminal gains and loses focus. By default, they pass the [`Event::FocusGained`] and
+    /// [`Event::FocusLost`] events on to the `on_event` hook. Disabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to report focus changes.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_focus_events(mut self, enabled: bool) -> Self {
+        self.config.focus_events = enabled;
+        self
+    }
+
+    /// Sets whether bracketed paste is enabled.
+    ///
+    /// When enabled, pasted text is delivered to screens at once through their `on_paste` hook,
+    /// which passes it on to the `on_event` hook as an [`Event::Paste`] by default, instead of one
+    /// key event per character. Disabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to enable bracketed paste.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_bracketed_paste(mut self, enabled: bool) -> Self {
+        self.config.bracketed_paste = enabled;
+        self
+    }
+
+    /// Sets the key that opens the help overlay.
+    ///
+    /// Pressing it shows the key bindings returned by the current screen's `help_text` hook in a
+    /// scrollable overlay drawn on top of the application, which is closed with the same key or
+    /// Esc. Screens without bindings receive the key through their `on_event` hook as usual.
+    /// Defaults to `?`.
+    ///
+    /// Parameters:
+    /// * `key` - The key that opens the help overlay.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the help key set.
+    pub fn with_help_key(mut self, key: KeyCode) -> Self {
+        self.config.help_key = key;
+        self
+    }
+
+    /// Adds a key that exits the application from any screen.
+    ///
+    /// Pressing it without modifiers other than Shift calls [`Navigator::exit()`] instead of
+    /// passing the key to the current screen, unless the screen's `consumes_quit_key` hook says
+    /// it wants it, like text inputs do. For shortcuts with modifiers, like Ctrl+C, use
+    /// [`App::with_global_event_handler()`] instead. There are no quit keys by default.
+    ///
+    /// Parameters:
+    /// * `key` - The key that exits the application.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the quit key added.
+    pub fn with_quit_key(mut self, key: KeyCode) -> Self {
+        self.config.quit_keys.push(key);
+        self
+    }
+
+    /// Adds several keys that exit the application from any screen.
+    ///
+    /// Check out [`App::with_quit_key()`] for more information.
+    ///
+    /// Parameters:
+    /// * `keys` - The keys that exit the application.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the quit keys added.
+    pub fn with_quit_keys(mut self, keys: &[KeyCode]) -> Self {
+        self.config.quit_keys.extend_from_slice(keys);
+        self
+    }
+
+    /// Sets whether re-draw requests are put on hold while the terminal is out of focus.
+    ///
+    /// When enabled, [`Navigator::redraw()`] calls made after an [`Event::FocusLost`] don't draw
+    /// anything until an [`Event::FocusGained`] arrives, at which point a single re-draw is done.
+    /// Screen tasks keep running, only drawing is suspended. Disabled by default.
+    ///
+    /// Note that terminals only send focus events when focus change reporting is enabled with
+    /// [`App::with_focus_events()`].
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to suspend re-draws while the terminal is out of focus.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_pause_on_focus_loss(mut self, enabled: bool) -> Self {
+        self.config.pause_on_focus_loss = enabled;
+        self
+    }
+
+    /// Limits how many frames are drawn per second.
+    ///
+    /// Re-draws requested faster than the limit are coalesced: the request is remembered, and a
+    /// single draw happens once the next frame is due. This keeps fast background tasks from
+    /// flooding the terminal. By default, the frame rate is not limited, and non-positive values
+    /// remove the limit.
+    ///
+    /// Parameters:
+    /// * `fps` - The maximum number of frames drawn per second.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the frame rate limit set.
+    pub fn with_frame_rate(mut self, fps: f64) -> Self {
+        self.config.frame_rate = Some(fps);
+        self
+    }
+
+    /// Sets the maximum time the terminal event reader waits for an event at once.
+    ///
+    /// By default, the event reader blocks until an event arrives. With a timeout, it polls for
+    /// events instead, which allows it to stop once the [`App`] is dropped, and the current
+    /// screen's `on_event_timeout` hook is called whenever the timeout passes without any event.
+    /// Useful for screens that refresh periodically while the user is idle, like dashboards.
+    ///
+    /// Parameters:
+    /// * `timeout` - The maximum time to wait for an event at once.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the event timeout set.
+    pub fn with_event_timeout(mut self, timeout: Duration) -> Self {
+        self.config.event_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the source of the events handled by the application, instead of the terminal.
+    ///
+    /// Useful to feed the application synthetic events, integrate another backend, or
+    /// pre-process events before screens see them. Check out [`EventSource`] for more
+    /// information. The terminal is still set up and drawn to as usual.
+    ///
+    /// Parameters:
+    /// * `source` - The source of the events.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the event source set.
+    pub fn with_event_source(mut self, source: impl EventSource) -> Self {
+        self.events = None;
+        self.config.event_source = Some(event_source_starter(source));
+        self
+    }
+
+    /// Sets whether frames are fully drawn off-screen before being written to the terminal.
+    ///
+    /// [`ratatui`] already writes only the cells that changed between frames, so this is mostly a
+    /// safeguard: the screen is drawn into an off-screen buffer first, and the terminal is only
+    /// touched once the frame is complete. Note that cursor positions set while drawing are lost
+    /// when enabled. Disabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to draw frames off-screen first.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the setting applied.
+    pub fn with_double_buffer(mut self, enabled: bool) -> Self {
+        self.config.double_buffer = enabled;
+        self
+    }
+
+    /// Sets a function to report rendering metrics to, every `every_n_frames` frames.
+    ///
+    /// The reporter is called with the [`RenderMetrics`] of the application right after a frame is
+    /// drawn, which is useful to tune the performance of screens that draw a lot. Screens can also
+    /// get the latest metrics with [`Navigator::render_metrics()`], with or without a reporter.
+    ///
+    /// ```ignore
+    /// let mut app = App::new().with_metrics_reporter(60, |metrics| {
+    ///     eprintln!("{} frames, {:?} each", metrics.frame_count, metrics.avg_frame_duration);
+    /// });
+    /// ```
+    ///
+    /// Parameters:
+    /// * `every_n_frames` - How many frames to draw between reports. `0` is treated as `1`.
+    /// * `reporter` - The function to report the metrics to.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the reporter set.
+    pub fn with_metrics_reporter(
+        mut self,
+        every_n_frames: u64,
+        reporter: impl Fn(RenderMetrics) + Send + Sync + 'static,
+    ) -> Self {
+        self.config.metrics_reporter = Some((every_n_frames.max(1), Box::new(reporter)));
+        self
+    }
+
+    /// Sets how long a screen lifecycle hook can take before it's logged as slow.
+    ///
+    /// Every call to `on_enter`, `on_exit`, `on_pause`, and `on_resume` is timed and logged with
+    /// [`tracing`]. Calls taking longer than the threshold are logged at the `WARN` level, and the
+    /// rest at the `DEBUG` level. Defaults to 100 milliseconds.
+    ///
+    /// Only available with the `tracing` feature.
+    ///
+    /// Parameters:
+    /// * `threshold` - The time after which a hook is considered slow.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the threshold applied.
+    #[cfg(feature = "tracing")]
+    pub fn with_slow_hook_threshold(mut self, threshold: Duration) -> Self {
+        self.config.slow_hook_threshold = threshold;
+        self
+    }
+}
+
+impl<T, E, S, ID> App<T, E, Handlers<S, ID, E>> {
+    /// Sets a function to handle the errors returned by screen lifecycle hooks.
+    ///
+    /// The handler is called with the error and a navigator every time a hook returns an error,
+    /// and decides whether the application keeps running or stops, returning the error from
+    /// [`App::run()`]. It can log the error, or push an error screen with the navigator. Without a
+    /// handler, errors are always propagated.
+    ///
+    /// It's only available once the screens type is set with [`App::with_screens()`], which gives
+    /// the navigator its screen ID type:
+    ///
+    /// ```ignore
+    /// let mut app = App::new()
+    ///     .with_screens::<AppScreens>()
+    ///     .with_error_handler(|err, navigator| {
+    ///         eprintln!("{err}");
+    ///         navigator.push(ScreenID::Error);
+    ///
+    ///         HookErrorAction::Continue
+    ///     });
+    /// ```
+    ///
+    /// Parameters:
+    /// * `handler` - The function deciding what to do with each error.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the error handler set.
+    pub fn with_error_handler(
+        mut self,
+        handler: impl Fn(&HookError, Navigator<ID, E>) -> HookErrorAction + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets a function to call when a screen can't be navigated to.
+    ///
+    /// The handler is called with the ID of the screen and a navigator whenever a screen's
+    /// `try_new` returns `None`, in which case the navigation action is dropped and the current
+    /// screen stays. It can show an error screen with the navigator, for example. Without a
+    /// handler, the action is silently dropped.
+    ///
+    /// It's only available once the screens type is set with [`App::with_screens()`].
+    ///
+    /// Parameters:
+    /// * `handler` - The function to call with the ID of the screen that couldn't be created.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the navigation error handler set.
+    pub fn with_navigation_error_handler(
+        mut self,
+        handler: impl Fn(ID, Navigator<ID, E>) + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.navigation_error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Adds a function handling events before they reach the current screen.
+    ///
+    /// Useful for app-wide shortcuts, like quitting with `Ctrl+Q` from any screen. The handler
+    /// returns `true` if it consumed the event, in which case it's not passed to the current
+    /// screen's `on_event` hook, or its dedicated hook like `on_resize`, or `false` to let it
+    /// through. The screens are still re-drawn after consumed resizes, and consumed focus changes
+    /// still pause drawing with [`App::with_pause_on_focus_loss()`].
+    ///
+    /// Handlers can be chained by calling this method multiple times. They're called in the order
+    /// they were added, until one of them consumes the event.
+    ///
+    /// It's only available once the screens type is set with [`App::with_screens()`]:
+    ///
+    /// ```ignore
+    /// let mut app = App::new()
+    ///     .with_screens::<AppScreens>()
+    ///     .with_global_event_handler(|event, navigator| {
+    ///         let Event::Key(key) = event else {
+    ///             return false;
+    ///         };
+    ///
+    ///         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('q') {
+    ///             navigator.exit();
+    ///             return true;
+    ///         }
+    ///
+    ///         false
+    ///     });
+    /// ```
+    ///
+    /// Parameters:
+    /// * `handler` - The function handling each event.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the event handler added.
+    pub fn with_global_event_handler(
+        mut self,
+        handler: impl Fn(Event, Navigator<ID, E>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.event_handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Adds a middleware intercepting navigation actions before they're processed.
+    ///
+    /// Middlewares can drop actions, for example to block navigating to screens the user isn't
+    /// allowed to see, or replace them with other ones. They're called in the order they were
+    /// added, each one with the action returned by the previous one. Check out
+    /// [`NavigatorMiddleware`] for more information.
+    ///
+    /// It's only available once the screens type is set with [`App::with_screens()`].
+    ///
+    /// Parameters:
+    /// * `middleware` - The middleware to add, which can be a closure taking an action and
+    ///   returning an optional one.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the middleware added.
+    pub fn with_middleware(
+        mut self,
+        middleware: impl NavigatorMiddleware<ID> + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Sets an async function creating a screen, instead of its `Default` implementation.
+    ///
+    /// Useful for screens with expensive constructors, like ones loading files or making network
+    /// requests, which would otherwise block the event loop while they're created:
+    ///
+    /// ```ignore
+    /// let mut app = App::new()
+    ///     .with_screens::<AppScreens>()
+    ///     .with_screen_factory(ScreenID::Report, || async {
+    ///         AppScreens::Report(ReportScreen::load("report.csv").await)
+    ///     });
+    /// ```
+    ///
+    /// The factory is called every time the screen is navigated to, and must return the variant
+    /// of the screens type matching `id`. When pushing or replacing a screen, it's only called
+    /// once the current screen's `can_leave` guard allowed the navigation, so leaving a screen
+    /// that refuses to be left doesn't create the new one. Its `can_enter` guard is still asked on
+    /// the screen the factory created. The first screen, and the one the application restarts
+    /// with, are still created with `Default`. It's only available once the screens type is set
+    /// with [`App::with_screens()`].
+    ///
+    /// Parameters:
+    /// * `id` - The ID of the screen the factory creates.
+    /// * `factory` - The function creating the screen.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the screen factory set.
+    pub fn with_screen_factory<F, Fut>(mut self, id: ID, factory: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = S> + 'static,
+    {
+        let factory: ScreenFactory<S> = Box::new(move || Box::pin(factory()));
+        self.handlers.screen_factories.push((id, factory));
+        self
+    }
+}
+
+impl<T, E, H> App<T, E, H> {
+    /// Installs a panic hook that restores the terminal before the panic message is printed.
+    ///
+    /// The hook disables mouse capture, focus change reporting, and bracketed paste, leaves the
+    /// alternate screen and raw mode, and then calls the previously installed panic hook, so the
+    /// message isn't garbled by the terminal state.
+    /// It's only installed once, no matter how many times this method is called, and it's also
+    /// installed when the application starts running, so this is only needed to restore the
+    /// terminal on panics happening before that.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance, after installing the hook.
+    pub fn with_panic_hook(self) -> Self {
+        install_panic_hook();
+        self
+    }
+
+    /// Consumes the application, returning its state.
+    ///
+    /// Returns:
+    /// `T` - The application state.
+    pub fn into_state(self) -> T {
+        self.state
+    }
+
+    /// Resets the application state to its default value when the application is restarted.
+    ///
+    /// By default, [`Navigator::restart()`] only resets the navigation stack, keeping the
+    /// application state as it was. With this, the state is reset too, which is useful for things
+    /// like logging out. It's reset once every screen has exited, right before the initial screen
+    /// is entered. Use [`App::with_restart_state()`] to reset it to something else.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the state reset on restarts.
+    pub fn with_state_reset_on_restart(self) -> Self
+    where
+        T: Default + 'static,
+    {
+        self.with_restart_state(T::default)
+    }
+
+    /// Resets the application state to the one returned by `f` when the application is
+    /// restarted.
+    ///
+    /// Behaves like [`App::with_state_reset_on_restart()`], but doesn't require the state to
+    /// implement `Default`, and the state can be reset to something other than its default value.
+    ///
+    /// Parameters:
+    /// * `f` - The function creating the new application state.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the state reset on restarts.
+    pub fn with_restart_state(mut self, f: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        self.restart_state = Some(Box::new(f));
+        self
+    }
+
+    /// Saves the application state to disk periodically.
+    ///
+    /// The state is serialized as JSON and written to `path` every `interval`, and once more
+    /// when the application exits, so users don't lose their progress if it's closed abruptly.
+    /// Use [`App::with_state_from_snapshot()`] to load it back on startup. Failing to save a
+    /// snapshot doesn't stop the application.
+    ///
+    /// Only available with the `persistence` feature.
+    ///
+    /// Parameters:
+    /// * `path` - The file to save the state to.
+    /// * `interval` - How often to save the state.
+    ///
+    /// Returns:
+    /// [`App`] - The application instance with the state snapshots enabled.
+    #[cfg(feature = "persistence")]
+    pub fn with_state_snapshot(mut self, path: impl AsRef<Path>, interval: Duration) -> Self
+    where
+        T: Serialize,
+    {
+        self.snapshot = Some(StateSnapshot::new(path.as_ref().to_path_buf(), interval));
+        self
+    }
+
+    /// Runs the main application loop like [`App::run()`], returning the final application state
+    /// once the application exits.
+    ///
+    /// Useful for wizards and setup flows that need to know what the user left things at.
+    ///
+    /// Returns:
+    /// `std::io::Result<T>` - The application state after the run, or the error that stopped it.
+    pub async fn run_with_result<S>(mut self) -> std::io::Result<T>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        self.run::<S>().await?;
+
+        Ok(self.into_state())
+    }
+
+    /// Runs the main application loop, handling events and screen re-drawing.
+    ///
+    /// Errors returned by screen lifecycle hooks stop the application unless an error handler
+    /// says otherwise (see [`App::with_error_handler()`]). They're returned wrapped in an
+    /// [`io::Error`] of kind [`io::ErrorKind::Other`].
+    ///
+    /// Once the screens type is set with [`App::with_screens()`], the application can only be run
+    /// with it, and it doesn't have to be given here.
+    ///
+    /// Returns:
+    /// `std::io::Result<()>` - Result of the application run.
+    pub async fn run<S>(&mut self) -> std::io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        self.run_in_terminal(S::default(), None, future::pending())
+            .await
+    }
+
+    /// Runs the main application loop like [`App::run()`], starting with the given screen instead
+    /// of the default one.
+    ///
+    /// Useful when the first screen needs some initialization [`Default`] can't do, like loading
+    /// a configuration file or taking command line arguments:
+    ///
+    /// ```ignore
+    /// let editor = EditorScreen::open(&args.path).await?;
+    ///
+    /// app.run_with_initial(AppScreens::Editor(editor)).await?;
+    /// ```
+    ///
+    /// [`Navigator::restart()`] creates a new screen with the ID of this one, the same way pushed
+    /// screens are created.
+    ///
+    /// Parameters:
+    /// * `screen` - The first screen to show.
+    ///
+    /// Returns:
+    /// `std::io::Result<()>` - Result of the application run.
+    pub async fn run_with_initial<S>(&mut self, screen: S) -> std::io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        self.run_in_terminal(screen, None, future::pending()).await
+    }
+
+    /// Runs the main application loop like [`App::run()`], drawing the screens in a part of the
+    /// terminal only.
+    ///
+    /// Screens see `area` as the whole terminal: it's what `frame.area()` returns when they're
+    /// drawn, and what their `preferred_area` hook is given. The rest of the terminal is left
+    /// blank. Useful to leave room for something drawn outside of ratapp, like a status line
+    /// printed after the application exits when the alternate screen is disabled.
+    ///
+    /// Parameters:
+    /// * `area` - The area of the terminal to draw the screens in. It's clamped to the terminal's
+    ///   size.
+    ///
+    /// Returns:
+    /// `std::io::Result<()>` - Result of the application run.
+    pub async fn run_in_area<S>(&mut self, area: Rect) -> std::io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        self.run_in_terminal(S::default(), Some(area), future::pending())
+            .await
+    }
+
+    /// Runs the main application loop like [`App::run()`], exiting gracefully when the process
+    /// is asked to terminate.
+    ///
+    /// On Unix, `SIGTERM` and `SIGINT` exit the application as if [`Navigator::exit()`] was
+    /// called, so every screen's `on_exit` hook runs and the terminal is restored before
+    /// returning. On other platforms, only Ctrl+C signals are listened for. Note that Ctrl+C
+    /// key presses reach screens as key events instead while the terminal is in raw mode.
+    ///
+    /// Only available with the `signal` feature.
+    ///
+    /// Returns:
+    /// `std::io::Result<()>` - Result of the application run.
+    #[cfg(feature = "signal")]
+    pub async fn run_until_signal<S>(&mut self) -> std::io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        let signal = shutdown_signal()?;
+
+        self.run_in_terminal(S::default(), None, signal).await
+    }
+
+    /// Runs the screens on the terminal from `initial`, in `area` if given, exiting once
+    /// `shutdown` completes if nothing else stops the application before.
+    async fn run_in_terminal<S>(
+        &mut self,
+        initial: S,
+        area: Option<Rect>,
+        shutdown: impl Future<Output = ()>,
+    ) -> io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        let mut terminal = self.config.init_terminal()?;
+        let mut events = self.take_events();
+
+        let result = self
+            .run_screens(
+                initial,
+                &mut terminal,
+                &mut events,
+                area,
+                shutdown,
+                RunMode::Terminal,
+            )
+            .await;
+
+        self.events = Some(events);
+        self.config.restore_terminal();
+
+        result
+    }
+
+    /// Runs the main application loop like [`App::run()`], drawing to a terminal set up by the
+    /// caller.
+    ///
+    /// Useful for other [`ratatui`] backends than crossterm, or to embed the application in a
+    /// terminal the caller manages. ratapp doesn't set up nor restore the terminal, so raw mode,
+    /// the alternate screen, mouse capture and the like are left to the caller, and screen titles
+    /// aren't set. Events are still read from the terminal with crossterm, unless another source
+    /// is set with [`App::with_event_source()`].
+    ///
+    /// Parameters:
+    /// * `terminal` - The terminal to draw the screens to.
+    ///
+    /// Returns:
+    /// `std::io::Result<()>` - Result of the application run.
+    pub async fn run_with_terminal<S, B>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+        B: Backend,
+    {
+        let mut events = self.take_events();
+
+        let result = self
+            .run_screens(
+                S::default(),
+                terminal,
+                &mut events,
+                None,
+                future::pending(),
+                RunMode::CustomTerminal,
+            )
+            .await;
+
+        self.events = Some(events);
+
+        result
+    }
+
+    /// Runs the application without a terminal, feeding it a predefined sequence of events.
+    ///
+    /// Screens are drawn to an 80x24 [`TestBackend`] instead of the terminal, and the events are
+    /// handled one by one as if they were read from it. The application exits once every event
+    /// and the navigation actions they caused have been handled, or when [`Navigator::exit()`] is
+    /// called, whichever happens first. Useful to test screens without a real terminal:
+    ///
+    /// ```ignore
+    /// let events = vec![Event::Key(KeyCode::Down.into()), Event::Key(KeyCode::Enter.into())];
+    /// let state = App::with_state(MyState::default())
+    ///     .run_headless::<MyScreens>(events)
+    ///     .await?;
+    ///
+    /// assert_eq!(state.selected, 1);
+    /// ```
+    ///
+    /// Parameters:
+    /// * `events` - The events to handle, in order.
+    ///
+    /// Returns:
+    /// `std::io::Result<T>` - The application state after the run, or the error that stopped it.
+    pub async fn run_headless<S>(mut self, events: Vec<Event>) -> std::io::Result<T>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+    {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24))?;
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        for event in events {
+            let _ = events_tx.send(event);
+        }
+        drop(events_tx);
+
+        self.run_screens(
+            S::default(),
+            &mut terminal,
+            &mut events_rx,
+            None,
+            future::pending(),
+            RunMode::Headless,
+        )
+        .await?;
+
+        Ok(self.into_state())
+    }
+
+    /// Returns the receiver of the events to handle, starting to read them if needed.
+    fn take_events(&mut self) -> mpsc::UnboundedReceiver<Event> {
+        match (self.events.take(), self.config.event_source.take()) {
+            (Some(events), _) => events,
+            (None, Some(start)) => start(),
+            (None, None) => spawn_event_reader(self.config.event_timeout),
+        }
+    }
+
+    /// Runs the screens until the application exits or fails, without restoring the terminal.
+    ///
+    /// `initial` is the first screen shown, and the one [`Navigator::restart()`] returns to.
+    /// Screens are drawn in `area` if given, or in the whole terminal otherwise.
+    ///
+    /// Once `shutdown` completes, the application exits as if [`Navigator::exit()`] was called.
+    /// Check out [`RunMode`] for how `mode` changes the loop.
+    async fn run_screens<S, B>(
+        &mut self,
+        initial: S,
+        terminal: &mut Terminal<B>,
+        events: &mut mpsc::UnboundedReceiver<Event>,
+        area: Option<Rect>,
+        shutdown: impl Future<Output = ()>,
+        mode: RunMode,
+    ) -> io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        H: HandlersFor<S, T, E>,
+        B: Backend,
+    {
+        let screens = VecDeque::from([ScreenEntry::new(initial)]);
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let (exit_tx, exit_rx) = oneshot::channel();
+        let (custom_events_tx, custom_events_rx) = mpsc::unbounded_channel::<E>();
+        let navigator = Navigator::new(events_tx, exit_tx, custom_events_tx);
+        Self::sync_stack(&navigator, &screens);
+
+        let mut session = Session {
+            initial_id: screens[0].id(),
+            screens,
+            forward: Vec::new(),
+            navigator,
+            events_rx,
+            exit_rx,
+            custom_events_rx,
+            handlers: self.handlers.take(),
+        };
+
+        // Every screen exits even if the application stops because of an error, so they can
+        // release what they hold, like files and background tasks.
+        let mut result = self
+            .run_loop(&mut session, terminal, events, area, shutdown, mode)
+            .await;
+
+        let Session {
+            screens,
+            navigator,
+            handlers,
+            ..
+        } = &mut session;
+
+        while let Some(mut old_screen) = screens.pop_back() {
+            let old_id = old_screen.id();
+            let on_exit = old_screen.on_exit(navigator.clone(), &mut self.state);
+            let on_exit = self
+                .config
+                .call_hook(handlers, "on_exit", old_id, navigator, on_exit);
+
+            let exited = match self.config.shutdown_timeout {
+                // A timed out hook is dropped so the rest of the screens can still exit.
+                Some(timeout) => match time::timeout(timeout, on_exit).await {
+                    Ok(exited) => exited,
+                    Err(_) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            screen = ?old_id,
+                            "Screen {old_id:?} on_exit timed out after {timeout:?}, skipping it"
+                        );
+                        Ok(())
+                    }
+                },
+                None => on_exit.await,
+            };
+
+            // The stack is only synced once the screen has exited, so that the tasks spawned by
+            // its `on_exit` are aborted along with it.
+            Self::sync_stack(navigator, screens);
+
+            // The first error is the one returned, the rest of the screens still exit.
+            result = result.and(exited);
+        }
+
+        #[cfg(feature = "persistence")]
+        self.save_snapshot(true);
+
+        navigator.run_post_action_hooks(&Action::Exit);
+        self.handlers.restore(session.handlers);
+
+        result
+    }
+
+    /// Runs the event loop of `session` until the application exits or fails, leaving the screens
+    /// still in the stack for [`App::run_screens()`] to exit.
+    async fn run_loop<S, B>(
+        &mut self,
+        session: &mut Session<S, S::ID, E>,
+        terminal: &mut Terminal<B>,
+        events: &mut mpsc::UnboundedReceiver<Event>,
+        area: Option<Rect>,
+        shutdown: impl Future<Output = ()>,
+        mode: RunMode,
+    ) -> io::Result<()>
+    where
+        S: ScreenState<T, E> + 'static,
+        E: Send + 'static,
+        B: Backend,
+    {
+        let Session {
+            initial_id,
+            screens,
+            forward,
+            navigator,
+            events_rx,
+            exit_rx,
+            custom_events_rx,
+            handlers,
+        } = session;
+        let (initial_id, navigator) = (*initial_id, navigator.clone());
+
+        let screen = screens.back_mut().unwrap();
+        let id = screen.id();
+        let on_enter = screen.on_enter(navigator.clone(), &mut self.state);
+        self.config
+            .call_hook(handlers, "on_enter", id, &navigator, on_enter)
+            .await?;
+
+        if mode == RunMode::Terminal {
+            self.update_title(&**screens.back().unwrap())?;
+        }
+
+        let mut draw = true;
+
+        // When the frame rate is limited, draws requested too early are postponed until
+        // `next_draw`.
+        let frame_interval = self.config.frame_interval();
+        let mut last_draw: Option<Instant> = None;
+        let mut metrics = RenderMetrics::default();
+
+        // Whether the terminal is out of focus and a re-draw was requested meanwhile, used when
+        // `pause_on_focus_loss` is enabled.
+        let mut unfocused = false;
+        let mut deferred_draw = false;
+
+        // The help overlay, while it's open.
+        let mut help: Option<HelpScreen> = None;
+
+        // An event received while `on_event` was handling the previous one, which cancelled it.
+        let mut pending_event: Option<Event> = None;
+
+        // Whether the last thing handled was an event rather than an action, so they can take
+        // turns with `EventPriority::Alternate`.
+        let mut handled_event = false;
+
+        let mut shutdown = pin!(shutdown);
+        let mut shutting_down = false;
+
+        // When the screen's `on_event_timeout` hook is due, if an event timeout was set.
+        let event_timeout = self.config.event_timeout;
+        let mut event_deadline = event_timeout.map(|timeout| Instant::now() + timeout);
+
+        // Ticks whenever the state should be saved, if `App::with_state_snapshot()` was used.
+        #[cfg(feature = "persistence")]
+        let mut snapshot_timer = self.snapshot.as_ref().map(|snapshot| {
+            time::interval_at(Instant::now() + snapshot.interval, snapshot.interval)
+        });
+        #[cfg(not(feature = "persistence"))]
+        let mut snapshot_timer: Option<time::Interval> = None;
+
+        loop {
+            if mode == RunMode::Headless
+                && events.is_closed()
+                && events.is_empty()
+                && pending_event.is_none()
+                && events_rx.is_empty()
+                && custom_events_rx.is_empty()
+            {
+                break;
+            }
+
+            navigator.increment_iteration_count();
+
+            let mut next_draw = None;
+
+            // Pending navigation actions are processed before drawing, so that a re-draw queued
+            // right before a navigation doesn't draw the screen being navigated away from.
+            let pending_actions = !events_rx.is_empty();
+
+            if draw
+                && !pending_actions
+                && let (Some(interval), Some(last_draw)) = (frame_interval, last_draw)
+                && last_draw.elapsed() < interval
+            {
+                next_draw = Some(last_draw + interval);
+            } else if draw && !pending_actions {
+                let mut attempts = 0;
+
+                // Overlays are drawn on top of the screens below them, down to the first screen
+                // that isn't an overlay.
+                let backdrop = screens
+                    .iter()
+                    .rposition(|entry| !entry.overlay && !entry.is_overlay(&self.state))
+                    .unwrap_or(0);
+
+                let draw_start = Instant::now();
+
+                while let Err(err) = draw_screens(
+                    terminal,
+                    screens.range_mut(backdrop..).map(|entry| &mut **entry),
+                    help.as_mut(),
+                    area,
+                    &navigator.peekable(),
+                    &self.state,
+                    self.config.double_buffer,
+                ) {
+                    match self.config.draw_error_policy {
+                        DrawErrorPolicy::RetryN(retries) if attempts < retries => {
+                            attempts += 1;
+                            time::sleep(DRAW_RETRY_DELAY).await;
+                        }
+                        _ => return Err(err),
+                    }
+                }
+
+                let elapsed = draw_start.elapsed();
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    screen = ?screens.back().expect("No screen in the stack!").id(),
+                    elapsed = ?elapsed,
+                    "screen drawn"
+                );
+
+                metrics.record(elapsed);
+                navigator.set_render_metrics(metrics);
+
+                if let Some((every_n_frames, reporter)) = &self.config.metrics_reporter
+                    && metrics.frame_count % every_n_frames == 0
+                {
+                    reporter(metrics);
+                }
+
+                draw = false;
+                last_draw = Some(Instant::now());
+            }
+
+            let ScreenEntry {
+                screen,
+                ticker,
+                ticks,
+                ..
+            } = screens.back_mut().expect("No screen in the stack!");
+
+            // The tick interval is restarted whenever the screen changes it.
+            let tick_interval = screen
+                .tick_interval(&self.state)
+                .filter(|interval| !interval.is_zero());
+            if ticker.as_ref().map(time::Interval::period) != tick_interval {
+                *ticker = tick_interval.map(|interval| {
+                    let mut ticker = time::interval_at(Instant::now() + interval, interval);
+                    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                    ticker
+                });
+            }
+
+            // Events are handled before actions unless the priority says otherwise, in which
+            // case they wait while actions are queued.
+            let events_first = match self.config.event_priority {
+                EventPriority::Events => true,
+                EventPriority::Actions => events_rx.is_empty(),
+                EventPriority::Alternate => !handled_event || events_rx.is_empty(),
+            };
+
+            tokio::select! {
+                // Exiting takes priority over everything else, including pending actions. The
+                // screen's task comes last, so that a task resolving right away can't keep the
+                // events and actions from being handled.
+                biased;
+
+                Ok(()) = &mut *exit_rx => break,
+                _ = &mut shutdown, if !shutting_down => {
+                    shutting_down = true;
+                    navigator.exit();
+                },
+                _ = time::sleep_until(next_draw.unwrap_or_else(Instant::now)),
+                    if next_draw.is_some() => {},
+                _ = tick(&mut snapshot_timer) => {
+                    #[cfg(feature = "persistence")]
+                    self.save_snapshot(false);
+                },
+                _ = tick(ticker) => {
+                    let id = screen.id();
+                    let on_tick = screen.on_tick(*ticks, navigator.clone(), &mut self.state);
+                    *ticks = ticks.wrapping_add(1);
+                    self.config.call_hook(handlers, "on_tick", id, &navigator, on_tick).await?;
+                },
+                _ = time::sleep_until(event_deadline.unwrap_or_else(Instant::now)),
+                    if event_deadline.is_some() => {
+                    event_deadline = event_timeout.map(|timeout| Instant::now() + timeout);
+
+                    let id = screen.id();
+                    let on_event_timeout =
+                        screen.on_event_timeout(navigator.clone(), &mut self.state);
+                    self.config
+                        .call_hook(handlers, "on_event_timeout", id, &navigator, on_event_timeout)
+                        .await?;
+                },
+                Some(event) = next_event(&mut pending_event, events), if events_first => {
+                    handled_event = true;
+                    event_deadline = event_timeout.map(|timeout| Instant::now() + timeout);
+
+                    // Resizes and focus changes affect the whole application, whether the screen
+                    // gets them or not.
+                    match event {
+                        Event::Resize(..) => draw = true,
+                        Event::FocusLost => unfocused |= self.config.pause_on_focus_loss,
+                        Event::FocusGained if unfocused => {
+                            unfocused = false;
+                            draw |= deferred_draw;
+                            deferred_draw = false;
+                        }
+                        _ => {}
+                    }
+
+                    // The help overlay takes every key press while it's open, and the quit keys
+                    // exit the application before reaching the screen.
+                    let handled = match (&mut help, &event) {
+                        (Some(open_help), Event::Key(key)) => {
+                            if key.kind == KeyEventKind::Press
+                                && open_help.handle_key(*key, self.config.help_key)
+                            {
+                                help = None;
+                            }
+
+                            draw = true;
+                            true
+                        }
+                        (None, Event::Key(key))
+                            if key.kind == KeyEventKind::Press
+                                && key.code == self.config.help_key =>
+                        {
+                            let bindings = screen.help_text(&self.state);
+                            let has_bindings = !bindings.is_empty();
+
+                            if has_bindings {
+                                help = Some(HelpScreen::new(bindings));
+                                draw = true;
+                            }
+
+                            has_bindings
+                        }
+                        (None, Event::Key(key))
+                            if key.kind == KeyEventKind::Press
+                                && (key.modifiers - KeyModifiers::SHIFT).is_empty()
+                                && self.config.quit_keys.contains(&key.code)
+                                && !screen.consumes_quit_key(&self.state) =>
+                        {
+                            navigator.exit();
+                            true
+                        }
+                        _ => false,
+                    };
+
+                    if handled
+                        || handlers.handle_global_event(&event, &navigator)
+                        || !screen.filter_event(&event, &self.state)
+                    {
+                        continue;
+                    }
+
+                    // Resizes, focus changes and pastes go to their own hooks instead of
+                    // `on_event`, which they pass them on to by default.
+                    let id = screen.id();
+                    match event {
+                        Event::Resize(cols, rows) => {
+                            let on_resize = screen.on_resize(
+                                cols,
+                                rows,
+                                navigator.clone(),
+                                &mut self.state,
+                            );
+                            self.config
+                                .call_hook(handlers, "on_resize", id, &navigator, on_resize)
+                                .await?;
+                        }
+                        Event::FocusGained => {
+                            let on_focus_gained =
+                                screen.on_focus_gained(navigator.clone(), &mut self.state);
+                            self.config
+                                .call_hook(
+                                    handlers,
+                                    "on_focus_gained",
+                                    id,
+                                    &navigator,
+                                    on_focus_gained,
+                                )
+                                .await?;
+                        }
+                        Event::FocusLost => {
+                            let on_focus_lost =
+                                screen.on_focus_lost(navigator.clone(), &mut self.state);
+                            self.config
+                                .call_hook(handlers, "on_focus_lost", id, &navigator, on_focus_lost)
+                                .await?;
+                        }
+                        Event::Paste(content) => {
+                            let on_paste =
+                                screen.on_paste(content, navigator.clone(), &mut self.state);
+                            self.config
+                                .call_hook(handlers, "on_paste", id, &navigator, on_paste)
+                                .await?;
+                        }
+                        event => {
+                            // A newer event arriving meanwhile cancels this one, and is handled
+                            // right after it.
+                            let mut on_event =
+                                pin!(screen.on_event(&event, navigator.clone(), &mut self.state));
+                            let result = loop {
+                                tokio::select! {
+                                    biased;
+
+                                    Some(next) = events.recv(), if pending_event.is_none() => {
+                                        pending_event = Some(next);
+                                        navigator.set_event_cancelled(true);
+                                    },
+                                    result = &mut on_event => break result,
+                                }
+                            };
+                            navigator.set_event_cancelled(false);
+
+                            handlers.handle_hook_result(result, &navigator)?;
+                        }
+                    }
+                },
+                Some(event) = custom_events_rx.recv(), if events_first => {
+                    handled_event = true;
+
+                    let id = screen.id();
+                    let on_custom_event =
+                        screen.on_custom_event(event, navigator.clone(), &mut self.state);
+                    self.config
+                        .call_hook(handlers, "on_custom_event", id, &navigator, on_custom_event)
+                        .await?;
+                },
+                Some(Command { action, mut on_complete, mut data }) = events_rx.recv() => {
+                    handled_event = false;
+
+                    // The actions of a batch are processed one after the other, without polling
+                    // for anything else in between.
+                    let actions = match action {
+                        Action::Batch(actions) => actions,
+                        action => vec![action],
+                    };
+
+                    for action in actions {
+                        let Some(action) = handlers.intercept(action) else {
+                            continue;
+                        };
+
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(action = ?action, "navigation action");
+
+                        // Actions that may change the current screen are traced as transitions,
+                        // from the current screen to the one on top of the stack afterwards.
+                        #[cfg(feature = "tracing")]
+                        let transition_start = Instant::now();
+                        #[cfg(feature = "tracing")]
+                        {
+                            self.config.transition_span = if matches!(
+                                action,
+                                Action::Push(_)
+                                    | Action::PushOverlay(_)
+                                    | Action::PushUnique(_)
+                                    | Action::Replace(_)
+                                    | Action::ReplaceWithFocus(_)
+                                    | Action::ReplaceRoot(_)
+                                    | Action::Back
+                                    | Action::Forward
+                                    | Action::PopTo(_)
+                                    | Action::PopToRoot
+                                    | Action::Clear
+                                    | Action::Restart
+                                    | Action::ReplaceHistory(_)
+                            ) {
+                                tracing::info_span!(
+                                    "screen_transition",
+                                    from = ?screens
+                                        .back()
+                                        .expect("No screen in the stack!")
+                                        .id(),
+                                    to = tracing::field::Empty,
+                                )
+                            } else {
+                                tracing::Span::none()
+                            };
+                        }
+
+                        let screen = screens.back_mut().expect("No screen in the stack!");
+
+                        match &action {
+                            Action::Push(id)
+                            | Action::PushOverlay(id)
+                            | Action::PushUnique(id) => {
+                                let current_id = screen.id();
+                                if matches!(action, Action::PushUnique(_)) && current_id == *id
+                                {
+                                    continue;
+                                }
+
+                                let Some(overflow) =
+                                    self.config.make_room(navigator.stack_depth() + 1, id)
+                                else {
+                                    continue;
+                                };
+
+                                // The current screen is asked first, so that the new one isn't
+                                // created for nothing.
+                                let can_leave =
+                                    screen.can_leave(navigator.clone(), &mut self.state);
+                                if !self
+                                    .config
+                                    .time_hook("can_leave", current_id, can_leave)
+                                    .await
+                                {
+                                    continue;
+                                }
+
+                                let Some(new_screen) =
+                                    self.create_screen(handlers, *id, &navigator).await
+                                else {
+                                    continue;
+                                };
+                                let mut new_screen = ScreenEntry::new(new_screen);
+                                new_screen.overlay = matches!(action, Action::PushOverlay(_));
+                                new_screen.data = data
+                                    .take()
+                                    .and_then(|data| data.downcast::<ScreenData>().ok())
+                                    .map(|data| *data);
+
+                                let can_enter =
+                                    new_screen.can_enter(navigator.clone(), &mut self.state);
+                                if !self.config.time_hook("can_enter", *id, can_enter).await {
+                                    continue;
+                                }
+
+                                let on_pause =
+                                    screen.on_pause(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(
+                                        handlers,
+                                        "on_pause",
+                                        current_id,
+                                        &navigator,
+                                        on_pause,
+                                    )
+                                    .await?;
+
+                                self.drop_oldest(overflow, handlers, &navigator, screens).await?;
+
+                                screens.push_back(new_screen);
+                                Self::sync_stack(&navigator, screens);
+
+                                let screen = screens.back_mut().unwrap();
+                                let on_enter =
+                                    screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", *id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            Action::Forward => {
+                                let Some(mut new_screen) = forward.pop() else {
+                                    continue;
+                                };
+                                let current_id = screen.id();
+                                let new_id = new_screen.id();
+
+                                let Some(overflow) =
+                                    self.config.make_room(navigator.stack_depth() + 1, new_id)
+                                else {
+                                    forward.push(new_screen);
+                                    continue;
+                                };
+
+                                let can_leave =
+                                    screen.can_leave(navigator.clone(), &mut self.state);
+                                if !self
+                                    .config
+                                    .time_hook("can_leave", current_id, can_leave)
+                                    .await
+                                {
+                                    forward.push(new_screen);
+                                    continue;
+                                }
+
+                                let can_enter =
+                                    new_screen.can_enter(navigator.clone(), &mut self.state);
+                                if !self
+                                    .config
+                                    .time_hook("can_enter", new_id, can_enter)
+                                    .await
+                                {
+                                    forward.push(new_screen);
+                                    continue;
+                                }
+
+                                navigator.set_forward_depth(forward.len());
+
+                                let on_pause =
+                                    screen.on_pause(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(
+                                        handlers,
+                                        "on_pause",
+                                        current_id,
+                                        &navigator,
+                                        on_pause,
+                                    )
+                                    .await?;
+
+                                self.drop_oldest(overflow, handlers, &navigator, screens).await?;
+
+                                screens.push_back(new_screen);
+                                Self::sync_stack(&navigator, screens);
+
+                                let screen = screens.back_mut().unwrap();
+                                let on_enter =
+                                    screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", new_id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            Action::ClearForward => {}
+                            Action::Replace(id) | Action::ReplaceWithFocus(id) => {
+                                let current_id = screen.id();
+
+                                let can_leave =
+                                    screen.can_leave(navigator.clone(), &mut self.state);
+                                if !self
+                                    .config
+                                    .time_hook("can_leave", current_id, can_leave)
+                                    .await
+                                {
+                                    continue;
+                                }
+
+                                let Some(new_screen) =
+                                    self.create_screen(handlers, *id, &navigator).await
+                                else {
+                                    continue;
+                                };
+                                let mut new_screen = ScreenEntry::new(new_screen);
+
+                                let can_enter =
+                                    new_screen.can_enter(navigator.clone(), &mut self.state);
+                                if !self.config.time_hook("can_enter", *id, can_enter).await {
+                                    continue;
+                                }
+
+                                let focus = match action {
+                                    Action::ReplaceWithFocus(_) => {
+                                        screen.capture_focus(&self.state)
+                                    }
+                                    _ => None,
+                                };
+
+                                let mut old_screen = screens.pop_back().unwrap();
+                                let old_id = old_screen.id();
+                                let on_exit =
+                                    old_screen.on_exit(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                    .await?;
+
+                                screens.push_back(new_screen);
+                                Self::sync_stack(&navigator, screens);
+
+                                let new_screen = screens.back_mut().unwrap();
+                                if let Some(focus) = focus {
+                                    new_screen.restore_focus(focus, &mut self.state);
+                                }
+
+                                let on_enter =
+                                    new_screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", *id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            Action::ReplaceRoot(id) if screens.len() > 1 => {
+                                let Some(new_root) =
+                                    self.create_screen(handlers, *id, &navigator).await
+                                else {
+                                    continue;
+                                };
+
+                                let mut old_root = screens.pop_front().unwrap();
+                                navigator.set_current_tasks(old_root.tasks.clone());
+
+                                let old_id = old_root.id();
+                                let on_exit =
+                                    old_root.on_exit(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                    .await?;
+
+                                // The new root is hidden behind the rest of the stack, so it's
+                                // only entered once the user goes back to it.
+                                let mut new_root = ScreenEntry::new(new_root);
+                                new_root.hidden = true;
+                                screens.push_front(new_root);
+                                Self::sync_stack(&navigator, screens);
+                            }
+                            Action::ReplaceRoot(id) => {
+                                let Some(new_screen) =
+                                    self.create_screen(handlers, *id, &navigator).await
+                                else {
+                                    continue;
+                                };
+
+                                let mut old_screen = screens.pop_back().unwrap();
+                                let old_id = old_screen.id();
+                                let on_exit =
+                                    old_screen.on_exit(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                    .await?;
+
+                                screens.push_back(ScreenEntry::new(new_screen));
+                                Self::sync_stack(&navigator, screens);
+
+                                let new_screen = screens.back_mut().unwrap();
+                                let on_enter =
+                                    new_screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", *id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            Action::Back => {
+                                if screens.len() > 1 {
+                                    let current_screen = screens.back_mut().unwrap();
+                                    let current_id = current_screen.id();
+                                    let can_leave = current_screen
+                                        .can_leave(navigator.clone(), &mut self.state);
+                                    if !self
+                                        .config
+                                        .time_hook("can_leave", current_id, can_leave)
+                                        .await
+                                    {
+                                        continue;
+                                    }
+
+                                    let below = screens.len() - 2;
+                                    let target_id = screens[below].id();
+                                    let can_enter = screens[below]
+                                        .can_enter(navigator.clone(), &mut self.state);
+                                    if !self
+                                        .config
+                                        .time_hook("can_enter", target_id, can_enter)
+                                        .await
+                                    {
+                                        continue;
+                                    }
+
+                                    // The stack is synced once the popped screen has exited,
+                                    // so that the tasks its `on_exit` spawns are aborted with it.
+                                    let mut old_screen = screens.pop_back().unwrap();
+                                    let old_id = old_screen.id();
+                                    let on_exit =
+                                        old_screen.on_exit(navigator.clone(), &mut self.state);
+                                    self.config
+                                        .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                        .await?;
+                                    Self::sync_stack(&navigator, screens);
+
+                                    // The popped screen is kept to go forward to, without the
+                                    // background tasks it spawned.
+                                    mem::replace(&mut old_screen.tasks, ManagedTasks::new())
+                                        .abort_all();
+                                    old_screen.ticker = None;
+                                    forward.push(old_screen);
+                                    navigator.set_forward_depth(forward.len());
+
+                                    let current_screen = screens.back_mut().unwrap();
+                                    let current_id = current_screen.id();
+
+                                    // A screen that was put in the stack without being shown is
+                                    // entered rather than resumed.
+                                    let entering = mem::take(&mut current_screen.hidden);
+                                    if entering {
+                                        let on_enter = current_screen
+                                            .on_enter(navigator.clone(), &mut self.state);
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_enter",
+                                                current_id,
+                                                &navigator,
+                                                on_enter,
+                                            )
+                                            .await?;
+                                    }
+
+                                    if let Some(data) = data.take() {
+                                        let on_receive = current_screen.on_receive(
+                                            data,
+                                            navigator.clone(),
+                                            &mut self.state,
+                                        );
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_receive",
+                                                current_id,
+                                                &navigator,
+                                                on_receive,
+                                            )
+                                            .await?;
+                                    }
+
+                                    if !entering {
+                                        let on_resume = current_screen.on_resume(
+                                            ResumeReason::Back(old_id),
+                                            navigator.clone(),
+                                            &mut self.state,
+                                        );
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_resume",
+                                                current_id,
+                                                &navigator,
+                                                on_resume,
+                                            )
+                                            .await?;
+                                    }
+
+                                    draw = true;
+                                }
+                            }
+                            Action::PopTo(_) | Action::PopToRoot => {
+                                let target = match &action {
+                                    Action::PopTo(id) => {
+                                        screens.iter().rposition(|s| s.id() == *id)
+                                    }
+                                    _ => Some(0),
+                                };
+
+                                if let Some(target) = target
+                                    && target + 1 < screens.len()
+                                {
+                                    let top_id = screens.back().unwrap().id();
+
+                                    while screens.len() > target + 1 {
+                                        let mut old_screen = screens.pop_back().unwrap();
+                                        let old_id = old_screen.id();
+                                        let on_exit = old_screen.on_exit(
+                                            navigator.clone(),
+                                            &mut self.state,
+                                        );
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_exit",
+                                                old_id,
+                                                &navigator,
+                                                on_exit,
+                                            )
+                                            .await?;
+                                        Self::sync_stack(&navigator, screens);
+                                    }
+
+                                    let current_screen = screens.back_mut().unwrap();
+                                    let current_id = current_screen.id();
+
+                                    // A screen that was put in the stack without being shown is
+                                    // entered rather than resumed.
+                                    if mem::take(&mut current_screen.hidden) {
+                                        let on_enter = current_screen
+                                            .on_enter(navigator.clone(), &mut self.state);
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_enter",
+                                                current_id,
+                                                &navigator,
+                                                on_enter,
+                                            )
+                                            .await?;
+                                    } else {
+                                        let reason = if let Action::PopTo(_) = action {
+                                            ResumeReason::PopTo(top_id)
+                                        } else {
+                                            ResumeReason::Cleared
+                                        };
+                                        let on_resume = current_screen.on_resume(
+                                            reason,
+                                            navigator.clone(),
+                                            &mut self.state,
+                                        );
+                                        self.config
+                                            .call_hook(
+                                                handlers,
+                                                "on_resume",
+                                                current_id,
+                                                &navigator,
+                                                on_resume,
+                                            )
+                                            .await?;
+                                    }
+
+                                    draw = true;
+                                }
+                            }
+                            Action::Clear => {
+                                while screens.len() > 1 {
+                                    let below = screens.len() - 2;
+                                    let mut old_screen = screens.remove(below).unwrap();
+                                    navigator.set_current_tasks(old_screen.tasks.clone());
+
+                                    let old_id = old_screen.id();
+                                    let on_exit =
+                                        old_screen.on_exit(navigator.clone(), &mut self.state);
+                                    self.config
+                                        .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                        .await?;
+                                    Self::sync_stack(&navigator, screens);
+                                }
+                            }
+                            Action::Restart => {
+                                let Some(new_screen) =
+                                    self.create_screen(handlers, initial_id, &navigator).await
+                                else {
+                                    continue;
+                                };
+
+                                while let Some(mut old_screen) = screens.pop_back() {
+                                    let old_id = old_screen.id();
+                                    let on_exit =
+                                        old_screen.on_exit(navigator.clone(), &mut self.state);
+                                    self.config
+                                        .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                        .await?;
+                                    Self::sync_stack(&navigator, screens);
+                                }
+
+                                if let Some(restart_state) = &self.restart_state {
+                                    self.state = restart_state();
+                                }
+
+                                screens.push_back(ScreenEntry::new(new_screen));
+                                Self::sync_stack(&navigator, screens);
+
+                                let new_screen = screens.back_mut().unwrap();
+                                let new_id = new_screen.id();
+                                let on_enter =
+                                    new_screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", new_id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            Action::ReplaceHistory(ids) if !ids.is_empty() => {
+                                let Some(overflow) =
+                                    self.config.make_room(ids.len(), ids[ids.len() - 1])
+                                else {
+                                    continue;
+                                };
+
+                                // The oldest screens above the root are left out to fit.
+                                let ids: Vec<_> = ids[..1]
+                                    .iter()
+                                    .chain(&ids[1 + overflow..])
+                                    .copied()
+                                    .collect();
+
+                                let mut new_screens = Vec::with_capacity(ids.len());
+                                for id in &ids {
+                                    match self.create_screen(handlers, *id, &navigator).await {
+                                        Some(new_screen) => new_screens.push(new_screen),
+                                        None => break,
+                                    }
+                                }
+                                if new_screens.len() < ids.len() {
+                                    continue;
+                                }
+
+                                while let Some(mut old_screen) = screens.pop_back() {
+                                    let old_id = old_screen.id();
+                                    let on_exit =
+                                        old_screen.on_exit(navigator.clone(), &mut self.state);
+                                    self.config
+                                        .call_hook(handlers, "on_exit", old_id, &navigator, on_exit)
+                                        .await?;
+                                    Self::sync_stack(&navigator, screens);
+                                }
+
+                                // Only the new current screen is entered, the ones below it are
+                                // entered once they're back on top.
+                                screens.extend(new_screens.into_iter().map(|new_screen| {
+                                    let mut new_screen = ScreenEntry::new(new_screen);
+                                    new_screen.hidden = true;
+                                    new_screen
+                                }));
+                                screens.back_mut().unwrap().hidden = false;
+
+                                Self::sync_stack(&navigator, screens);
+
+                                let new_screen = screens.back_mut().unwrap();
+                                let new_id = new_screen.id();
+                                let on_enter =
+                                    new_screen.on_enter(navigator.clone(), &mut self.state);
+                                self.config
+                                    .call_hook(handlers, "on_enter", new_id, &navigator, on_enter)
+                                    .await?;
+
+                                draw = true;
+                            }
+                            // An empty stack would leave no current screen.
+                            Action::ReplaceHistory(_) => {}
+                            // Exits are usually sent through `exit_rx` instead, and only arrive
+                            // here as part of a batch. The actions before it already had their
+                            // post-action hooks called, the rest of the batch is dropped, and
+                            // post-action hooks are called with the exit once every screen has
+                            // exited.
+                            Action::Exit => {
+                                navigator.exit();
+
+                                if let Some(on_complete) = on_complete.take() {
+                                    on_complete();
+                                }
+
+                                break;
+                            }
+                            Action::Redraw => {
+                                if unfocused {
+                                    deferred_draw = true;
+                                } else {
+                                    draw = true;
+                                }
+                            }
+                            Action::SetTitle(title) => {
+                                if mode == RunMode::Terminal {
+                                    execute!(io::stdout(), SetTitle(title))?;
+                                }
+                            }
+                            // Batches are flattened above, and can't be nested.
+                            Action::Batch(_) => {}
+                        }
+
+                        // Screens set their title whenever they become the current screen.
+                        if mode == RunMode::Terminal
+                            && matches!(
+                                action,
+                                Action::Push(_)
+                                    | Action::PushOverlay(_)
+                                    | Action::PushUnique(_)
+                                    | Action::Replace(_)
+                                    | Action::ReplaceWithFocus(_)
+                                    | Action::ReplaceRoot(_)
+                                    | Action::Back
+                                    | Action::Forward
+                                    | Action::PopTo(_)
+                                    | Action::PopToRoot
+                                    | Action::Restart
+                                    | Action::ReplaceHistory(_)
+                            )
+                        {
+                            self.update_title(&**screens.back().unwrap())?;
+                        }
+
+                        // Navigating anywhere other than back and forth discards the screens to
+                        // go forward to, like browsers do.
+                        if matches!(
+                            action,
+                            Action::Push(_)
+                                | Action::PushOverlay(_)
+                                | Action::PushUnique(_)
+                                | Action::Replace(_)
+                                | Action::ReplaceWithFocus(_)
+                                | Action::ReplaceRoot(_)
+                                | Action::PopTo(_)
+                                | Action::PopToRoot
+                                | Action::Clear
+                                | Action::Restart
+                                | Action::ReplaceHistory(_)
+                                | Action::ClearForward
+                        ) {
+                            forward.clear();
+                            navigator.set_forward_depth(0);
+                        }
+
+                        #[cfg(feature = "tracing")]
+                        {
+                            let span = &self.config.transition_span;
+                            if !span.is_disabled() {
+                                let to = screens.back().unwrap().id();
+                                span.record("to", tracing::field::debug(to));
+                                span.in_scope(|| {
+                                    tracing::debug!(
+                                        duration_us = %transition_start.elapsed().as_micros(),
+                                        "screen transition finished"
+                                    );
+                                });
+                            }
+                        }
+
+                        navigator.run_post_action_hooks(&action);
+
+                        if let Some(on_complete) = on_complete.take() {
+                            on_complete();
+                        }
+                    }
+
+                    // Transitions dropped by the screens' guards don't reach the end of the
+                    // loop.
+                    #[cfg(feature = "tracing")]
+                    {
+                        self.config.transition_span = tracing::Span::none();
+                    }
+                },
+                _ = screen.task(navigator.clone(), &mut self.state) => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exits the `count` oldest screens above the root, to make room for new ones.
+    async fn drop_oldest<S>(
+        &mut self,
+        count: usize,
+        handlers: &Handlers<S, S::ID, E>,
+        navigator: &Navigator<S::ID, E>,
+        screens: &mut VecDeque<ScreenEntry<S>>,
+    ) -> io::Result<()>
+    where
+        S: ScreenState<T, E>,
+    {
+        for mut oldest in screens.drain(1..1 + count).collect::<Vec<_>>() {
+            // The tasks it spawns while exiting are aborted with it.
+            navigator.set_current_tasks(oldest.tasks.clone());
+
+            let oldest_id = oldest.id();
+            let on_exit = oldest.on_exit(navigator.clone(), &mut self.state);
+            self.config
+                .call_hook(handlers, "on_exit", oldest_id, navigator, on_exit)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the screen with the given ID, with its factory if one was set with
+    /// [`App::with_screen_factory()`], or with [`ScreenState::try_new()`] otherwise.
+    ///
+    /// If the screen can't be created, the navigation error handler is called and `None` is
+    /// returned.
+    async fn create_screen<S>(
+        &self,
+        handlers: &Handlers<S, S::ID, E>,
+        id: S::ID,
+        navigator: &Navigator<S::ID, E>,
+    ) -> Option<S>
+    where
+        S: ScreenState<T, E>,
+    {
+        let factory = handlers
+            .screen_factories
+            .iter()
+            .find(|(factory_id, _)| *factory_id == id);
+
+        let screen = match factory {
+            Some((_, factory)) => Some(factory().await),
+            None => S::try_new(id),
+        };
+
+        if screen.is_none()
+            && let Some(handler) = &handlers.navigation_error_handler
+        {
+            handler(id, navigator.clone());
+        }
+
+        screen
+    }
+
+    /// Sets the terminal title to the one of the given screen, if it has one.
+    fn update_title<S>(&self, screen: &S) -> io::Result<()>
+    where
+        S: ScreenState<T, E>,
+    {
+        if let Some(title) = screen.title(&self.state) {
+            execute!(io::stdout(), SetTitle(title))?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates what the navigator knows about the navigation stack after it changes.
+    fn sync_stack<S>(navigator: &Navigator<S::ID, E>, screens: &VecDeque<ScreenEntry<S>>)
+    where
+        S: ScreenState<T, E>,
+    {
+        navigator.set_stack(screens.iter().map(|entry| entry.id()));
+
+        if let Some(current) = screens.back() {
+            navigator.set_current_tasks(current.tasks.clone());
+        }
+
+        navigator.set_screen_data(screens.back().and_then(|current| current.data.clone()));
+        navigator.set_screen_info(
+            screens
+                .back()
+                .map(|current| (current.name(), current.description())),
+        );
+    }
+
+    /// Saves the application state to disk if [`App::with_state_snapshot()`] was used.
+    ///
+    /// Periodic snapshots are written in a blocking task so that the event loop isn't held up,
+    /// while the final one is written right away. Failing to save a snapshot isn't fatal, so
+    /// errors are only logged when the `tracing` feature is enabled.
+    #[cfg(feature = "persistence")]
+    fn save_snapshot(&self, blocking: bool) {
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+
+        let result = snapshot.serialize(&self.state).and_then(|data| {
+            if blocking {
+                persistence::write(&snapshot.path, &data)
+            } else {
+                let path = snapshot.path.clone();
+                tokio::task::spawn_blocking(move || {
+                    if let Err(_err) = persistence::write(&path, &data) {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("Failed to save the state snapshot: {_err}");
+                    }
+                });
+
+                Ok(())
+            }
+        });
+
+        if let Err(_err) = result {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Failed to save the state snapshot: {_err}");
+        }
+    }
+}
+
+/// How [`App::run_screens()`] runs the screens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    /// On the terminal set up by ratapp, whose title is set by the screens.
+    Terminal,
+    /// On a terminal set up by the caller, whose backend may not support titles.
+    CustomTerminal,
+    /// On a test backend, exiting once the events channel is closed and every event and
+    /// navigation action has been handled.
+    Headless,
+}
+
+/// The screens run by [`App::run_screens()`], along with the navigator they're given and the
+/// receiving ends of its channels.
+struct Session<S, ID, E> {
+    /// The ID of the first screen shown, which [`Navigator::restart()`] returns to.
+    initial_id: ID,
+    screens: VecDeque<ScreenEntry<S>>,
+    /// The screens popped with [`Navigator::back()`], the last one being the first to go forward
+    /// to.
+    forward: Vec<ScreenEntry<S>>,
+    navigator: Navigator<ID, E>,
+    events_rx: mpsc::UnboundedReceiver<Command<ID>>,
+    exit_rx: oneshot::Receiver<()>,
+    custom_events_rx: mpsc::UnboundedReceiver<E>,
+    /// The handlers taken from the [`App`] for the run.
+    handlers: Handlers<S, ID, E>,
+}
+
+/// A screen in the navigation stack, along with the background tasks it spawned with
+/// [`Navigator::spawn_managed()`], which are aborted when it's dropped.
+struct ScreenEntry<S> {
+    screen: S,
+    tasks: ManagedTasks,
+    /// Whether the screen was pushed with [`Navigator::push_overlay()`].
+    overlay: bool,
+    /// Ticks at the screen's tick interval, if it has one.
+    ticker: Option<time::Interval>,
+    /// How many times the screen has ticked.
+    ticks: u64,
+    /// The data the screen was pushed with by [`Navigator::push_with_data()`].
+    data: Option<ScreenData>,
+    /// Whether the screen was created without being shown, by [`Navigator::replace_root()`] or
+    /// [`Navigator::replace_history()`], and hasn't been shown yet.
+    hidden: bool,
+}
+
+impl<S> ScreenEntry<S> {
+    fn new(screen: S) -> Self {
+        ScreenEntry {
+            screen,
+            tasks: ManagedTasks::new(),
+            overlay: false,
+            ticker: None,
+            ticks: 0,
+            data: None,
+            hidden: false,
+        }
+    }
+}
+
+impl<S> Deref for ScreenEntry<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.screen
+    }
+}
+
+impl<S> DerefMut for ScreenEntry<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.screen
+    }
+}
+
+impl<S> Drop for ScreenEntry<S> {
+    fn drop(&mut self) {
+        self.tasks.abort_all();
+    }
+}
+
+/// Listens for the signals asking the process to terminate, returning a future that completes
+/// once one arrives.
+#[cfg(feature = "signal")]
+fn shutdown_signal() -> io::Result<impl Future<Output = ()>> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        // The listeners are registered right away, so no signal is missed while the application
+        // starts.
+        let mut terminate = signal(SignalKind::terminate())?;
+        let mut interrupt = signal(SignalKind::interrupt())?;
+
+        Ok(async move {
+            tokio::select! {
+                _ = terminate.recv() => {},
+                _ = interrupt.recv() => {},
+            }
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                future::pending::<()>().await;
+            }
+        })
+    }
+}
+
+/// Waits for the next tick of `timer`, or forever if there's no timer.
+async fn tick(timer: &mut Option<time::Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+/// Receives the next event, starting with the one that cancelled the previous event, if any.
+async fn next_event(
+    pending: &mut Option<Event>,
+    events: &mut mpsc::UnboundedReceiver<Event>,
+) -> Option<Event> {
+    match pending.take() {
+        Some(event) => Some(event),
+        None => events.recv().await,
+    }
+}
+
+/// Installs a panic hook restoring the terminal, unless it's already installed.
+pub(crate) fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+
+    INSTALLED.call_once(|| {
+        let hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let _ = execute!(
+                io::stdout(),
+                DisableMouseCapture,
+                DisableFocusChange,
+                DisableBracketedPaste
+            );
+            ratatui::restore();
+            hook(info);
+        }));
+    });
+}
+
+/// Spawns a blocking task reading terminal events, returning the channel they're sent through.
+///
+/// When a timeout is given, the task polls for events instead of blocking indefinitely, so it can
+/// notice when the receiver is dropped and stop.
+fn spawn_event_reader(timeout: Option<Duration>) -> mpsc::UnboundedReceiver<Event> {
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+
+    tokio::task::spawn_blocking(move || {
+        loop {
+            if let Some(timeout) = timeout
+                && !event::poll(timeout).unwrap_or(false)
+            {
+                if events_tx.is_closed() {
+                    break;
+                }
+
+                continue;
+            }
+
+            if let Ok(event) = event::read()
+                && events_tx.send(event).is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    events_rx
+}
+
+/// Returns a function spawning the task reading `source`, once the application starts running.
+pub(crate) fn event_source_starter(source: impl EventSource) -> EventSourceStarter {
+    // The source only has to be `Send`, the mutex makes it `Sync` for the configuration.
+    let source = Mutex::new(source);
+
+    Box::new(move || {
+        let mut source = source
+            .into_inner()
+            .expect("Failed to lock the event source mutex");
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Some(event) = source.next_event().await {
+                if events_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        events_rx
+    })
+}
+
+impl<T, E, H> fmt::Debug for App<T, E, H>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("App")
+            .field("state", &self.state)
+            .field(
+                "pending_events",
+                &self.events.as_ref().map_or(0, |events| events.len()),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Draws screens to the terminal, one on top of the other, and the help overlay on top of them
+/// if it's open. They're drawn in `area` if given, or in the whole terminal otherwise.
+///
+/// When `double_buffer` is set, the whole frame is drawn off-screen first and then copied into the
+/// terminal's frame at once.
+fn draw_screens<'a, S, T, E, B>(
+    terminal: &mut Terminal<B>,
+    screens: impl IntoIterator<Item = &'a mut S>,
+    help: Option<&mut HelpScreen>,
+    area: Option<Rect>,
+    navigator: &PeekableNavigator<S::ID>,
+    state: &T,
+    double_buffer: bool,
+) -> io::Result<()>
+where
+    S: ScreenState<T, E> + 'a,
+    B: Backend,
+{
+    let size = terminal.size()?;
+
+    let draw = |frame: &mut Frame| {
+        let root = area.map_or(frame.area(), |area| area.intersection(frame.area()));
+        let context = DrawContext {
+            stack_depth: navigator.depth(),
+            current_id: navigator.top_id().expect("No screen in the stack!"),
+            is_overlay: false,
+            terminal_size: (root.width, root.height),
+            navigator: navigator.clone(),
+        };
+
+        // The screens above the first one are overlays, drawn on top of it.
+        for (index, screen) in screens.into_iter().enumerate() {
+            let area = screen.preferred_area(root, state).intersection(root);
+            let context = DrawContext {
+                is_overlay: index > 0,
+                ..context.clone()
+            };
+            draw_in_area(frame, area, |frame| {
+                screen.draw_with_context(frame, context, state)
+            });
+        }
+
+        if let Some(help) = help {
+            draw_in_area(frame, root, |frame| help.draw(frame));
+        }
+    };
+
+    if double_buffer {
+        let buffer = draw_offscreen(Rect::from((Position::ORIGIN, size)), draw);
+        terminal.draw(|frame| frame.buffer_mut().merge(&buffer))?;
+    } else {
+        terminal.draw(draw)?;
+    }
+
+    Ok(())
+}
+
+/// Draws on the given area of the frame, as if it was the whole frame.
+///
+/// When the area is smaller than the frame, the drawing is done on an off-screen frame whose
+/// `area()` is the given area, and then merged into the real frame's buffer.
+fn draw_in_area(frame: &mut Frame, area: Rect, draw: impl FnOnce(&mut Frame)) {
+    let area = area.intersection(frame.area());
+
+    if area == frame.area() {
+        draw(frame);
+        return;
+    }
+
+    frame.buffer_mut().merge(&draw_offscreen(area, draw));
+}
+
+/// Draws on an off-screen frame whose `area()` is the given area, returning the drawn buffer.
+fn draw_offscreen(area: Rect, draw: impl FnOnce(&mut Frame)) -> Buffer {
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(area),
+    };
+    let mut offscreen = Terminal::with_options(TestBackend::new(area.width, area.height), options)
+        .expect("Creating an off-screen terminal can't fail");
+
+    draw(&mut offscreen.get_frame());
+
+    mem::take(offscreen.current_buffer_mut())
 }
 
 impl<T> Default for App<T>
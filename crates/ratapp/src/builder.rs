@@ -0,0 +1,552 @@
+//! A builder to configure and create [`App`] instances.
+//!
+//! [`App::new()`] and [`App::with_state()`] create an [`App`] with the default configuration.
+//! When you need more control over how the application runs, use an [`AppBuilder`] instead.
+
+use std::{marker::PhantomData, time::Duration};
+
+use ratatui::crossterm::event::{Event, KeyCode};
+
+use crate::{
+    app::{
+        App, Config, DrawErrorPolicy, EventPriority, Handlers, HookErrorAction, NoHandlers,
+        RenderMetrics, ScreenFactory, StackOverflowPolicy, event_source_starter,
+        install_panic_hook,
+    },
+    event_source::EventSource,
+    navigation::{Navigator, NavigatorMiddleware},
+    screen::{HookError, ScreenState},
+};
+
+/// A builder for [`App`] instances.
+///
+/// Create one with [`AppBuilder::new()`], chain the configuration methods you need, and call
+/// [`AppBuilder::build()`] to get the [`App`]:
+///
+/// ```ignore
+/// let mut app = AppBuilder::new()
+///     .state(MyAppState::default())
+///     .mouse(true)
+///     .fps_limit(60)
+///     .title("My App")
+///     .build();
+///
+/// app.run::<MyScreens>().await?;
+/// ```
+///
+/// The handlers taking screen IDs, like [`AppBuilder::error_handler()`], are only available once
+/// the screens type is set with [`AppBuilder::screens()`].
+pub struct AppBuilder<T = (), E = (), H = NoHandlers> {
+    state: T,
+    config: Config,
+    handlers: H,
+    custom_events: PhantomData<fn(E)>,
+}
+
+impl AppBuilder<()> {
+    /// Creates a new `AppBuilder` without any application state and the default configuration.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - A new application builder.
+    pub fn new() -> Self {
+        AppBuilder {
+            state: (),
+            config: Config::default(),
+            handlers: NoHandlers,
+            custom_events: PhantomData,
+        }
+    }
+}
+
+impl<T, E> AppBuilder<T, E> {
+    /// Sets the global application state.
+    ///
+    /// Check out [`ScreenWithState`](crate::ScreenWithState) to learn how to use it from screens.
+    ///
+    /// Parameters:
+    /// * `state` - The initial application state.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the state set.
+    pub fn state<U>(self, state: U) -> AppBuilder<U, E> {
+        AppBuilder {
+            state,
+            config: self.config,
+            handlers: NoHandlers,
+            custom_events: PhantomData,
+        }
+    }
+
+    /// Sets the type of the custom events sent with [`Navigator::send_event()`].
+    ///
+    /// Check out [`App::with_custom_events()`] for more information.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the custom event type set.
+    pub fn custom_events<F>(self) -> AppBuilder<T, F> {
+        AppBuilder {
+            state: self.state,
+            config: self.config,
+            handlers: NoHandlers,
+            custom_events: PhantomData,
+        }
+    }
+
+    /// Sets the screens type the application is run with.
+    ///
+    /// Check out [`App::with_screens()`] for more information. The state and the custom event
+    /// type must be set before.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the screens type set.
+    pub fn screens<S>(self) -> AppBuilder<T, E, Handlers<S, S::ID, E>>
+    where
+        S: ScreenState<T, E>,
+    {
+        AppBuilder {
+            state: self.state,
+            config: self.config,
+            handlers: Handlers::default(),
+            custom_events: PhantomData,
+        }
+    }
+}
+
+impl<T, E, H> AppBuilder<T, E, H> {
+    /// Limits how many frames are drawn per second.
+    ///
+    /// Re-draws requested faster than the limit are coalesced into a single draw. By default, the
+    /// frame rate is not limited.
+    ///
+    /// Parameters:
+    /// * `fps` - The maximum number of frames drawn per second.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the frame rate limit set.
+    pub fn fps_limit(mut self, fps: u32) -> Self {
+        self.config.frame_rate = Some(fps.into());
+        self
+    }
+
+    /// Limits how many frames are drawn per second, allowing fractional frame rates.
+    ///
+    /// Works like [`AppBuilder::fps_limit()`]. Non-positive values remove the limit.
+    ///
+    /// Parameters:
+    /// * `fps` - The maximum number of frames drawn per second.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the frame rate limit set.
+    pub fn frame_rate(mut self, fps: f64) -> Self {
+        self.config.frame_rate = Some(fps);
+        self
+    }
+
+    /// Sets whether mouse events are captured.
+    ///
+    /// When enabled, mouse events are delivered to screens through their `on_event` hook.
+    /// Disabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to capture mouse events.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn mouse(mut self, enabled: bool) -> Self {
+        self.config.mouse = enabled;
+        self
+    }
+
+    /// Sets whether the terminal reports focus changes.
+    ///
+    /// Check out [`App::with_focus_events()`] for more information.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to report focus changes.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn focus_events(mut self, enabled: bool) -> Self {
+        self.config.focus_events = enabled;
+        self
+    }
+
+    /// Sets whether bracketed paste is enabled.
+    ///
+    /// Check out [`App::with_bracketed_paste()`] for more information.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to enable bracketed paste.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn bracketed_paste(mut self, enabled: bool) -> Self {
+        self.config.bracketed_paste = enabled;
+        self
+    }
+
+    /// Sets whether the application is drawn on the terminal's alternate screen.
+    ///
+    /// The alternate screen keeps the terminal's content intact while the application runs, and
+    /// restores it on exit. Enabled by default.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to use the alternate screen.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn alternate_screen(mut self, enabled: bool) -> Self {
+        self.config.alternate_screen = enabled;
+        self
+    }
+
+    /// Sets whether the terminal is put in raw mode while the application runs.
+    ///
+    /// Check out [`App::with_raw_mode()`] for more information.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to enable raw mode.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn raw_mode(mut self, enabled: bool) -> Self {
+        self.config.raw_mode = enabled;
+        self
+    }
+
+    /// Sets the terminal window title shown while the application runs.
+    ///
+    /// Parameters:
+    /// * `title` - The terminal window title.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the title set.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.config.title = Some(title.into());
+        self
+    }
+
+    /// Sets the key that opens the help overlay.
+    ///
+    /// Check out [`App::with_help_key()`] for more information.
+    ///
+    /// Parameters:
+    /// * `key` - The key that opens the help overlay.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the help key set.
+    pub fn help_key(mut self, key: KeyCode) -> Self {
+        self.config.help_key = key;
+        self
+    }
+
+    /// Adds a key that exits the application from any screen.
+    ///
+    /// Check out [`App::with_quit_key()`] for more information.
+    ///
+    /// Parameters:
+    /// * `key` - The key that exits the application.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the quit key added.
+    pub fn quit_key(mut self, key: KeyCode) -> Self {
+        self.config.quit_keys.push(key);
+        self
+    }
+
+    /// Adds several keys that exit the application from any screen.
+    ///
+    /// Check out [`App::with_quit_key()`] for more information.
+    ///
+    /// Parameters:
+    /// * `keys` - The keys that exit the application.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the quit keys added.
+    pub fn quit_keys(mut self, keys: &[KeyCode]) -> Self {
+        self.config.quit_keys.extend_from_slice(keys);
+        self
+    }
+
+    /// Sets the maximum time the terminal event reader waits for an event at once.
+    ///
+    /// Check out [`App::with_event_timeout()`] for more information.
+    ///
+    /// Parameters:
+    /// * `timeout` - The maximum time to wait for an event at once.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the event timeout set.
+    pub fn event_timeout(mut self, timeout: Duration) -> Self {
+        self.config.event_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum time each screen's `on_exit` hook may take when the application exits.
+    ///
+    /// Check out [`App::with_shutdown_timeout()`] for more information.
+    ///
+    /// Parameters:
+    /// * `timeout` - The maximum time each `on_exit` hook may take on shutdown.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the shutdown timeout set.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.config.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets what to do when drawing a screen to the terminal fails.
+    ///
+    /// Check out [`DrawErrorPolicy`] for the available policies.
+    ///
+    /// Parameters:
+    /// * `policy` - The policy to apply on draw errors.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the policy set.
+    pub fn draw_error_policy(mut self, policy: DrawErrorPolicy) -> Self {
+        self.config.draw_error_policy = policy;
+        self
+    }
+
+    /// Sets whether terminal events or navigation actions are handled first when both are
+    /// waiting.
+    ///
+    /// Check out [`EventPriority`] for the available priorities.
+    ///
+    /// Parameters:
+    /// * `priority` - What to handle first.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the priority set.
+    pub fn event_priority(mut self, priority: EventPriority) -> Self {
+        self.config.event_priority = priority;
+        self
+    }
+
+    /// Sets the maximum number of screens in the navigation stack.
+    ///
+    /// Check out [`App::with_max_stack_depth()`] for more information.
+    ///
+    /// Parameters:
+    /// * `depth` - The maximum number of screens in the stack, including the root screen.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the maximum depth set.
+    pub fn max_stack_depth(mut self, depth: usize) -> Self {
+        self.config.max_stack_depth = Some(depth);
+        self
+    }
+
+    /// Sets what to do when a screen is pushed onto a full navigation stack.
+    ///
+    /// Check out [`StackOverflowPolicy`] for the available policies.
+    ///
+    /// Parameters:
+    /// * `policy` - What to do with pushes onto a full stack.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the policy set.
+    pub fn stack_overflow_policy(mut self, policy: StackOverflowPolicy) -> Self {
+        self.config.stack_overflow_policy = policy;
+        self
+    }
+
+    /// Sets whether re-draw requests are put on hold while the terminal is out of focus.
+    ///
+    /// Check out [`App::with_pause_on_focus_loss()`] for more information.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to suspend re-draws while the terminal is out of focus.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn pause_on_focus_loss(mut self, enabled: bool) -> Self {
+        self.config.pause_on_focus_loss = enabled;
+        self
+    }
+
+    /// Sets whether frames are fully drawn off-screen before being written to the terminal.
+    ///
+    /// Check out [`App::with_double_buffer()`] for more information.
+    ///
+    /// Parameters:
+    /// * `enabled` - Whether to draw frames off-screen first.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the setting applied.
+    pub fn double_buffer(mut self, enabled: bool) -> Self {
+        self.config.double_buffer = enabled;
+        self
+    }
+
+    /// Sets a function to report rendering metrics to, every `every_n_frames` frames.
+    ///
+    /// Check out [`App::with_metrics_reporter()`] for more information.
+    ///
+    /// Parameters:
+    /// * `every_n_frames` - How many frames to draw between reports. `0` is treated as `1`.
+    /// * `reporter` - The function to report the metrics to.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the reporter set.
+    pub fn metrics_reporter(
+        mut self,
+        every_n_frames: u64,
+        reporter: impl Fn(RenderMetrics) + Send + Sync + 'static,
+    ) -> Self {
+        self.config.metrics_reporter = Some((every_n_frames.max(1), Box::new(reporter)));
+        self
+    }
+
+    /// Sets how long a screen lifecycle hook can take before it's logged as slow.
+    ///
+    /// Check out [`App::with_slow_hook_threshold()`] for more information. Only available with
+    /// the `tracing` feature.
+    ///
+    /// Parameters:
+    /// * `threshold` - The time after which a hook is considered slow.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the threshold applied.
+    #[cfg(feature = "tracing")]
+    pub fn slow_hook_threshold(mut self, threshold: Duration) -> Self {
+        self.config.slow_hook_threshold = threshold;
+        self
+    }
+}
+
+impl<T, E, S, ID> AppBuilder<T, E, Handlers<S, ID, E>> {
+    /// Sets a function to handle the errors returned by screen lifecycle hooks.
+    ///
+    /// Check out [`App::with_error_handler()`] for more information.
+    ///
+    /// Parameters:
+    /// * `handler` - The function deciding what to do with each error.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the error handler set.
+    pub fn error_handler(
+        mut self,
+        handler: impl Fn(&HookError, Navigator<ID, E>) -> HookErrorAction + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets a function to call when a screen can't be navigated to.
+    ///
+    /// Check out [`App::with_navigation_error_handler()`] for more information.
+    ///
+    /// Parameters:
+    /// * `handler` - The function to call with the ID of the screen that couldn't be created.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the navigation error handler set.
+    pub fn navigation_error_handler(
+        mut self,
+        handler: impl Fn(ID, Navigator<ID, E>) + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.navigation_error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Adds a function handling events before they reach the current screen.
+    ///
+    /// Check out [`App::with_global_event_handler()`] for more information.
+    ///
+    /// Parameters:
+    /// * `handler` - The function handling each event.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the event handler added.
+    pub fn global_event_handler(
+        mut self,
+        handler: impl Fn(Event, Navigator<ID, E>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.event_handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Adds a middleware intercepting navigation actions before they're processed.
+    ///
+    /// Check out [`App::with_middleware()`] for more information.
+    ///
+    /// Parameters:
+    /// * `middleware` - The middleware to add.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the middleware added.
+    pub fn middleware(
+        mut self,
+        middleware: impl NavigatorMiddleware<ID> + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Sets an async function creating a screen, instead of its `Default` implementation.
+    ///
+    /// Check out [`App::with_screen_factory()`] for more information.
+    ///
+    /// Parameters:
+    /// * `id` - The ID of the screen the factory creates.
+    /// * `factory` - The function creating the screen.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the screen factory set.
+    pub fn screen_factory<F, Fut>(mut self, id: ID, factory: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = S> + 'static,
+    {
+        let factory: ScreenFactory<S> = Box::new(move || Box::pin(factory()));
+        self.handlers.screen_factories.push((id, factory));
+        self
+    }
+}
+
+impl<T, E, H> AppBuilder<T, E, H> {
+    /// Sets the source of the events handled by the application, instead of the terminal.
+    ///
+    /// Check out [`App::with_event_source()`] for more information.
+    ///
+    /// Parameters:
+    /// * `source` - The source of the events.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder with the event source set.
+    pub fn event_source(mut self, source: impl EventSource) -> Self {
+        self.config.event_source = Some(event_source_starter(source));
+        self
+    }
+
+    /// Installs a panic hook that restores the terminal before the panic message is printed.
+    ///
+    /// Check out [`App::with_panic_hook()`] for more information.
+    ///
+    /// Returns:
+    /// [`AppBuilder`] - The builder, after installing the hook.
+    pub fn panic_hook(self) -> Self {
+        install_panic_hook();
+        self
+    }
+
+    /// Creates the [`App`] with the configured state and options.
+    ///
+    /// Returns:
+    /// [`App`] - A new application instance.
+    pub fn build(self) -> App<T, E, H> {
+        App::from_config(self.state, self.config, self.handlers)
+    }
+}
+
+impl<T> Default for AppBuilder<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        AppBuilder::new().state(T::default())
+    }
+}
@@ -0,0 +1,50 @@
+//! Pluggable sources of the terminal events handled by the application loop.
+
+use std::future::Future;
+
+use ratatui::crossterm::event::{self, Event};
+
+/// A source of the events handled by the application loop, set with
+/// [`App::with_event_source()`](crate::App::with_event_source).
+///
+/// By default, events are read from the terminal with crossterm. Implement this trait to feed the
+/// application synthetic events, integrate another backend, or pre-process events before screens
+/// see them, like remapping keys:
+///
+/// ```ignore
+/// struct VimKeys(CrosstermEventSource);
+///
+/// impl EventSource for VimKeys {
+///     async fn next_event(&mut self) -> Option<Event> {
+///         let event = self.0.next_event().await?;
+///
+///         Some(match event {
+///             Event::Key(k) if k.code == KeyCode::Char('j') => Event::Key(KeyCode::Down.into()),
+///             Event::Key(k) if k.code == KeyCode::Char('k') => Event::Key(KeyCode::Up.into()),
+///             event => event,
+///         })
+///     }
+/// }
+/// ```
+///
+/// The source is read in a background task, so it may be asked for the next event before the
+/// previous one is handled.
+pub trait EventSource: Send + 'static {
+    /// Waits for the next event, returning `None` once the source won't produce any more.
+    fn next_event(&mut self) -> impl Future<Output = Option<Event>> + Send;
+}
+
+/// An [`EventSource`] reading events from the terminal with crossterm, like the application does
+/// by default.
+///
+/// Useful to wrap in other sources that pre-process the terminal's events. Each event is read on
+/// tokio's blocking thread pool, so reading doesn't block the runtime. It stops producing events
+/// once reading from the terminal fails.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrosstermEventSource;
+
+impl EventSource for CrosstermEventSource {
+    async fn next_event(&mut self) -> Option<Event> {
+        tokio::task::spawn_blocking(event::read).await.ok()?.ok()
+    }
+}
@@ -0,0 +1,78 @@
+//! Extension traits with convenience methods built on top of the base ratapp API.
+//!
+//! Import them all at once with `use ratapp::prelude::*`.
+
+use std::time::Duration;
+
+use crate::navigation::Navigator;
+
+/// Convenience methods for common navigation patterns, built on top of [`Navigator`]'s API.
+///
+/// The current screen is the one as of the last processed navigation action. Actions sent but not
+/// yet processed by the application loop aren't taken into account, so calling these methods
+/// several times in a row from the same hook may not behave as expected.
+pub trait NavigatorExt<ID> {
+    /// Pushes a screen onto the navigation stack, unless it's already the current screen.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to push onto the stack.
+    fn push_if_not_current(&self, id: ID);
+
+    /// Pushes `b` if the current screen is `a`, and `a` otherwise.
+    ///
+    /// Arguments:
+    /// * `a` - The ID of the first screen.
+    /// * `b` - The ID of the second screen.
+    fn toggle_between(&self, a: ID, b: ID);
+
+    /// Replaces the whole navigation stack with a screen, which becomes the root.
+    ///
+    /// Every screen in the stack exits, the current one included, so going back from the new
+    /// screen does nothing. It's a shorthand for [`Navigator::replace_history()`] with a single
+    /// screen.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the new root screen.
+    fn push_root(&self, id: ID);
+
+    /// Exits the application after the given delay.
+    ///
+    /// Must be called from within the tokio runtime running the application.
+    ///
+    /// Arguments:
+    /// * `delay` - How long to wait before exiting.
+    fn exit_after(&self, delay: Duration);
+}
+
+impl<ID, E> NavigatorExt<ID> for Navigator<ID, E>
+where
+    ID: Copy + PartialEq + Send + 'static,
+    E: Send + 'static,
+{
+    fn push_if_not_current(&self, id: ID) {
+        if self.current_id() != Some(id) {
+            self.push(id);
+        }
+    }
+
+    fn toggle_between(&self, a: ID, b: ID) {
+        if self.current_id() == Some(a) {
+            self.push(b);
+        } else {
+            self.push(a);
+        }
+    }
+
+    fn push_root(&self, id: ID) {
+        self.replace_history(vec![id]);
+    }
+
+    fn exit_after(&self, delay: Duration) {
+        let navigator = self.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            navigator.exit();
+        });
+    }
+}
@@ -0,0 +1,29 @@
+//! Common event filters, to implement [`Screen::filter_event()`] with.
+//!
+//! ```
+//! use ratapp::{Screen, filter};
+//! use ratatui::crossterm::event::Event;
+//!
+//! #[derive(Default)]
+//! struct MenuScreen;
+//!
+//! impl Screen<()> for MenuScreen {
+//!     fn filter_event(&self, event: &Event) -> bool {
+//!         filter::keys_only(event)
+//!     }
+//! }
+//! ```
+//!
+//! [`Screen::filter_event()`]: crate::Screen::filter_event
+
+use ratatui::crossterm::event::Event;
+
+/// Passes key events only.
+pub fn keys_only(event: &Event) -> bool {
+    matches!(event, Event::Key(_))
+}
+
+/// Passes all events but mouse events.
+pub fn no_mouse(event: &Event) -> bool {
+    !matches!(event, Event::Mouse(_))
+}
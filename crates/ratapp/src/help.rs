@@ -0,0 +1,115 @@
+//! The built-in help overlay listing the current screen's key bindings.
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Clear, Row, Table},
+};
+
+/// The help overlay opened with the help key, set with [`App::with_help_key()`].
+///
+/// It holds a snapshot of the key bindings returned by the screen's `help_text()` when it was
+/// opened, and is drawn on top of the whole application until it's closed.
+///
+/// [`App::with_help_key()`]: crate::App::with_help_key
+pub(crate) struct HelpScreen {
+    bindings: Vec<(KeyCode, &'static str)>,
+    /// The index of the first binding shown.
+    scroll: usize,
+    /// How many bindings fit in the overlay as of the last draw, used to clamp the scroll.
+    visible: usize,
+}
+
+impl HelpScreen {
+    pub(crate) fn new(bindings: Vec<(KeyCode, &'static str)>) -> Self {
+        HelpScreen {
+            bindings,
+            scroll: 0,
+            visible: 0,
+        }
+    }
+
+    /// Draws the overlay centered on the frame.
+    pub(crate) fn draw(&mut self, frame: &mut Frame) {
+        let key_width = self
+            .bindings
+            .iter()
+            .map(|(key, _)| key.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let description_width = self
+            .bindings
+            .iter()
+            .map(|(_, description)| description.len())
+            .max()
+            .unwrap_or(0);
+
+        // Two columns of content, the spacing between them, and the borders.
+        let width = (key_width + description_width + 3).max(20) as u16 + 2;
+        let height = self.bindings.len() as u16 + 2;
+        let area = centered(frame.area(), width, height);
+
+        self.visible = area.height.saturating_sub(2) as usize;
+        self.scroll = self
+            .scroll
+            .min(self.bindings.len().saturating_sub(self.visible));
+
+        let rows = self.bindings[self.scroll..]
+            .iter()
+            .map(|(key, description)| {
+                Row::new([
+                    Line::styled(key.to_string(), Style::new().add_modifier(Modifier::BOLD)),
+                    Line::raw(*description),
+                ])
+            });
+
+        let table = Table::new(
+            rows,
+            [Constraint::Length(key_width as u16), Constraint::Fill(1)],
+        )
+        .column_spacing(3)
+        .block(
+            Block::bordered()
+                .title(" Help ")
+                .title_bottom(Line::raw(" Esc to close ").right_aligned()),
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(table, area);
+    }
+
+    /// Handles a key press while the overlay is open, returning whether it should be closed.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent, help_key: KeyCode) -> bool {
+        let last = self.bindings.len().saturating_sub(self.visible);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            code if code == help_key => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.visible),
+            KeyCode::PageDown => self.scroll = (self.scroll + self.visible).min(last),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = last,
+            _ => {}
+        }
+
+        false
+    }
+}
+
+/// Returns an area of the given size centered on `area`, shrunk to fit it if necessary.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,82 @@
+//! A simpler view of the terminal events, for screens that don't need all of crossterm's details.
+
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
+
+/// A terminal event, with the most common cases easier to pattern match than crossterm's
+/// [`Event`].
+///
+/// It's what the `on_input` hook of [`Screen`](crate::Screen) and
+/// [`ScreenWithState`](crate::ScreenWithState) receives, converted from the [`Event`] passed to
+/// `on_event`:
+///
+/// ```ignore
+/// async fn on_input(&mut self, input: InputEvent, navigator: Navigator<ScreenID>) -> HookResult {
+///     match input {
+///         InputEvent::Key { code: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL } => {
+///             self.save().await?;
+///         }
+///         InputEvent::Key { code: KeyCode::Esc, .. } => navigator.back(),
+///         _ => {}
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputEvent {
+    /// A key was pressed, or is held down and repeating. Key releases, only reported by some
+    /// terminals, are [`InputEvent::Other`] events.
+    Key {
+        /// The key.
+        code: KeyCode,
+        /// The modifier keys held down along with it.
+        modifiers: KeyModifiers,
+    },
+    /// A mouse event. Only received when mouse capture is enabled.
+    Mouse(MouseEvent),
+    /// The terminal was resized to the given number of columns and rows.
+    Resize(u16, u16),
+    /// Text was pasted into the terminal. Only received when bracketed paste is enabled.
+    Paste(String),
+    /// Any other event, like focus changes and key releases.
+    Other(Event),
+}
+
+impl From<Event> for InputEvent {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            }) => InputEvent::Key { code, modifiers },
+            Event::Mouse(mouse) => InputEvent::Mouse(mouse),
+            Event::Resize(cols, rows) => InputEvent::Resize(cols, rows),
+            Event::Paste(content) => InputEvent::Paste(content),
+            event => InputEvent::Other(event),
+        }
+    }
+}
+
+/// Converts a borrowed event, only cloning the text of [`Event::Paste`] since the rest of the
+/// events don't allocate.
+impl From<&Event> for InputEvent {
+    fn from(event: &Event) -> Self {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            }) => InputEvent::Key {
+                code: *code,
+                modifiers: *modifiers,
+            },
+            Event::Mouse(mouse) => InputEvent::Mouse(*mouse),
+            Event::Resize(cols, rows) => InputEvent::Resize(*cols, *rows),
+            Event::Paste(content) => InputEvent::Paste(content.clone()),
+            event => InputEvent::Other(event.clone()),
+        }
+    }
+}
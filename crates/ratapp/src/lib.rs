@@ -10,6 +10,23 @@
 //! It currently only supports [`tokio`] as the async runtime and
 //! [`crossterm`](https://docs.rs/crossterm) as the terminal backend for [`ratatui`].
 //!
+//! `ratapp` requires the standard library. [`ratatui`], [`tokio`], and crossterm all depend on it,
+//! so `no_std` targets are not supported, and the code generated by the [`Screens`] derive macro
+//! is not `no_std`-compatible either.
+//!
+//! Enable the `tracing` feature to log how long each screen lifecycle hook takes with
+//! [`tracing`](https://docs.rs/tracing). Use `App::with_slow_hook_threshold()` to tune which
+//! hooks are reported as slow. Screens being entered and exited are logged at the `INFO` level,
+//! navigation actions at the `DEBUG` level, and draws at the `TRACE` level. Navigation actions
+//! that change screens are processed in a `screen_transition` span, with `from` and `to` fields
+//! holding the IDs of the screens, which the hooks they call are logged in.
+//!
+//! Enable the `persistence` feature to save the application state to disk periodically with
+//! `App::with_state_snapshot()`, and load it back with `App::with_state_from_snapshot()`.
+//!
+//! Enable the `signal` feature to exit gracefully when the process is asked to terminate with
+//! `App::run_until_signal()`.
+//!
 //! > NOTE: Ratapp is still in early development. APIs may change in future releases.
 //!
 //! # Installation
@@ -70,19 +87,15 @@
 //!
 //! #[derive(Screens)]
 //! pub enum AppScreens {
+//!     #[screen(default)]
 //!     Home(home::HomeScreen),
 //! }
-//!
-//! impl Default for AppScreens {
-//!     fn default() -> Self {
-//!         AppScreens::Home(home::HomeScreen::default())
-//!     }
-//! }
 //! ```
 //!
 //! Note that we haven't implemented the `HomeScreen` struct yet; we'll do that next. The
-//! [`Default`] implementation is required by `ratapp` to know which screen to display first when
-//! the application starts.
+//! `#[screen(default)]` attribute tells `ratapp` which screen to display first when the
+//! application starts, by implementing [`Default`] for the enum. You can also implement
+//! [`Default`] manually instead.
 //!
 //! ## A Barebones [`Screen`]
 //!
@@ -91,7 +104,7 @@
 //! as needed.
 //!
 //! ```ignore
-//! use ratapp::{Navigator, Screen};
+//! use ratapp::{DrawContext, HookResult, Navigator, Screen};
 //! use ratatui::{Frame, crossterm::event::Event};
 //!
 //! use crate::screens::ScreenID;
@@ -102,16 +115,22 @@
 //! }
 //!
 //! impl Screen<ScreenID> for HomeScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         // Drawing logic will go here.
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         // Terminal-event-handling logic will go here.
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
 //!
+//! Lifecycle hooks like `on_event` return a [`HookResult`], so errors can be bubbled up with `?`.
+//! By default, an error stops the application and is returned from [`App::run()`]. Check out
+//! [`App::with_error_handler()`] to handle them differently.
+//!
 //! ## `ScreenID`
 //!
 //! Perfect! Now we have our first screen set up. There's something off though; Did you notice we
@@ -137,10 +156,12 @@
 //! ## A Simple Screen
 //!
 //! Now yes, let's draw something. Our screen right now is empty, so let's add some content to it.
-//! Let's update our `draw` method to draw a simple paragraph.
+//! Let's implement the `draw_with_context` method to draw a simple paragraph. Its [`DrawContext`]
+//! tells the screen how it's being drawn, like how deep it is in the navigation stack, but we
+//! don't need it for now.
 //!
 //! ```
-//! # use ratapp::{App, Navigator, Screen};
+//! # use ratapp::{App, DrawContext, HookResult, Navigator, Screen};
 //! use ratatui::{Frame, crossterm::event::Event, widgets::Paragraph, text::Line};
 //!
 //! # enum ScreenID {}
@@ -148,7 +169,7 @@
 //! # struct HomeScreen { counter: u32 }
 //! #
 //! impl Screen<ScreenID> for HomeScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         let text = Paragraph::new(
 //!             vec![
 //!                 Line::from("Hello ratapp!"),
@@ -164,8 +185,10 @@
 //!        frame.render_widget(text, frame.area());
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         // Terminal-event-handling logic will go here.
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -177,7 +200,7 @@
 //! presses.
 //!
 //! ```
-//! # use ratapp::{App, Navigator, Screen};
+//! # use ratapp::{App, DrawContext, HookResult, Navigator, Screen};
 //! use ratatui::{Frame, crossterm::event::{Event, KeyCode}, widgets::Paragraph};
 //!
 //! # enum ScreenID {}
@@ -185,11 +208,11 @@
 //! # struct HomeScreen { counter: u32 }
 //! #
 //! impl Screen<ScreenID> for HomeScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         // -- Drawing logic as before --
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         if let Event::Key(key_event) = event {
 //!             match key_event.code {
 //!                 KeyCode::Up => {
@@ -203,6 +226,8 @@
 //!
 //!             navigator.redraw(); // Add this line to trigger a re-draw after handling the event.
 //!         }
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -212,7 +237,7 @@
 //! is sent, and by calling `navigator.redraw()` we trigger a redraw with our updated screen
 //! state. That's why you'll see the screen updating its numbers when you press the arrow keys.
 //! 
-//! [`Screen::draw()`] is only called when a redraw is needed, so it won't be called on every event
+//! [`Screen::draw_with_context()`] is only called when a redraw is needed, so it won't be called on every event
 //! unless you explicitly request it with `navigator.redraw()`. This helps optimize performance by
 //! avoiding unnecessary redraws.
 //!
@@ -270,22 +295,17 @@
 //!
 //! #[derive(Screens)]
 //! pub enum AppScreens {
+//!     #[screen(default)]
 //!     Home(home::HomeScreen),
 //!     List(list::ListScreen),  // And this!
 //! }
-//!
-//! impl Default for AppScreens {
-//!     fn default() -> Self {
-//!         AppScreens::Home(home::HomeScreen::default())
-//!     }
-//! }
 //! ```
 //!
 //! That's all we need to do here. Perfect. Now, same as with our home screen, let's create a new
 //! screen in our new `list` module.
 //!
 //! ```ignore
-//! use ratapp::{Navigator, Screen};
+//! use ratapp::{DrawContext, HookResult, Navigator, Screen};
 //! use ratatui::{Frame, crossterm::event::Event};
 //!
 //! use crate::screens::ScreenID;
@@ -294,12 +314,14 @@
 //! struct ListScreen;
 //!
 //! impl Screen<ScreenID> for ListScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         // Drawing logic will go here.
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         // Terminal-event-handling logic will go here.
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -347,7 +369,7 @@
 //! and add an exit option in the process.
 //!
 //! ```
-//! # use ratapp::{App, Navigator, Screen};
+//! # use ratapp::{App, DrawContext, HookResult, Navigator, Screen};
 //! use ratatui::{Frame, crossterm::event::{Event, KeyCode}, widgets::Paragraph, text::Line};
 //!
 //! # enum ScreenID { List }
@@ -355,7 +377,7 @@
 //! # struct HomeScreen { counter: u32 }
 //! #
 //! impl Screen<ScreenID> for HomeScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         let text = Paragraph::new(
 //!             vec![
 //!                 Line::from("Hello ratapp!"),
@@ -373,7 +395,7 @@
 //!        frame.render_widget(text, frame.area());
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         if let Event::Key(key_event) = event {
 //!              match key_event.code {
 //!                 KeyCode::Up => {
@@ -393,6 +415,8 @@
 //!
 //!             navigator.redraw();
 //!         }
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -411,7 +435,7 @@
 //! exit.
 //!
 //! ```
-//! # use ratapp::{Navigator, Screen};
+//! # use ratapp::{DrawContext, HookResult, Navigator, Screen};
 //! # use ratatui::{Frame, crossterm::event::{Event, KeyCode}};
 //! #
 //! # #[derive(Default)]
@@ -420,11 +444,11 @@
 //! # enum ScreenID { Home }
 //! #
 //! impl Screen<ScreenID> for ListScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         // Drawing logic will go here.
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         if let Event::Key(key_event) = event {  // Add this!
 //!             match key_event.code {              // Add this!
 //!                 KeyCode::Enter => {             // Add this!
@@ -438,6 +462,8 @@
 //!                                                 // Add this!
 //!             navigator.redraw();                 // Add this!
 //!         }                                       // Add this!
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -461,7 +487,7 @@
 //! bit more user-friendly, since without guides our user wouldn't know how to use our app.
 //!
 //! ```
-//! # use ratapp::{Navigator, Screen};
+//! # use ratapp::{DrawContext, HookResult, Navigator, Screen};
 //! # use ratatui::{
 //! #     Frame,
 //! #     crossterm::event::Event,
@@ -476,7 +502,7 @@
 //! # enum ScreenID {}
 //! #
 //! impl Screen<ScreenID> for ListScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         let layout = Layout::vertical([
 //!             Constraint::Length(3),
 //!             Constraint::Length(1), // Gap
@@ -504,8 +530,10 @@
 //!         frame.render_widget(text, text_area);
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         // Our previous code...
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -514,8 +542,8 @@
 //! we have our pretty list drawn on the screen, let's make the arrows change the selected item!
 //!
 //! ```
-//! # use ratapp::{Navigator, Screen};
-//! # use ratatui::{Frame, crossterm::event::{Event, KeyCode}, widgets::ListState};
+//! # use ratapp::{HookResult, Navigator, Screen};
+//! # use ratatui::{crossterm::event::{Event, KeyCode}, widgets::ListState};
 //! #
 //! # #[derive(Default)]
 //! # struct ListScreen { state: ListState }
@@ -523,9 +551,7 @@
 //! # enum ScreenID { Home }
 //! #
 //! impl Screen<ScreenID> for ListScreen {
-//! #   fn draw(&mut self, frame: &mut Frame) {}
-//! #
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>) {
+//!     async fn on_event(&mut self, event: &Event, navigator: Navigator<ScreenID>) -> HookResult {
 //!         if let Event::Key(key_event) = event {
 //!             match key_event.code {
 //!                 KeyCode::Up => {                   // Add this!
@@ -551,6 +577,8 @@
 //!
 //!             navigator.redraw();
 //!         }
+//!
+//!         Ok(())
 //!     }
 //! }
 //! ```
@@ -608,8 +636,8 @@
 //!
 //! For example:
 //!
-//! ```
-//! use ratapp::{App, Navigator, ScreenWithState, Screens};
+//! ```no_run
+//! use ratapp::{App, DrawContext, HookResult, Navigator, ScreenWithState, Screens};
 //! use ratatui::{Frame, crossterm::event::Event};
 //!
 //! enum Theme {
@@ -623,25 +651,32 @@
 //!
 //! #[derive(Screens)]
 //! pub enum MyScreens {
+//!    #[screen(default)]
 //!    Home(HomeScreen),
 //! }
 //!
-//! impl Default for MyScreens {
-//!     fn default() -> Self {
-//!         MyScreens::Home(HomeScreen::default())
-//!     }
-//! }
-//!
 //! #[derive(Default)]
 //! struct HomeScreen;
 //!
 //! impl ScreenWithState<ScreenID, State> for HomeScreen {
-//!     fn draw(&mut self, frame: &mut Frame, state: &State) {
+//!     fn draw_with_context(
+//!         &mut self,
+//!         frame: &mut Frame,
+//!         _context: DrawContext<ScreenID>,
+//!         state: &State,
+//!     ) {
 //!         // Use state.theme to determine colors, etc.
 //!     }
 //!
-//!     async fn on_event(&mut self, event: Event, navigator: Navigator<ScreenID>, state: &mut State) {
+//!     async fn on_event(
+//!         &mut self,
+//!         event: &Event,
+//!         navigator: Navigator<ScreenID>,
+//!         state: &mut State,
+//!     ) -> HookResult {
 //!         // Modify state.theme based on user input, etc.
+//!
+//!         Ok(())
 //!     }
 //! }
 //!
@@ -664,13 +699,15 @@
 //! like:
 //!
 //! ```
-//! use ratapp::{App, Navigator, Screen, Screens};
+//! use ratapp::{App, DrawContext, HookResult, Navigator, Screen, Screens};
 //! use ratatui::{
 //!     Frame,
 //!     crossterm::event::{Event, KeyCode},
 //!     text::Text,
 //! };
 //! use std::time::Duration;
+//! #
+//! # enum ScreenID {}
 //! 
 //! fn get_tick(tick: usize) -> char {
 //!     match tick % 4 {
@@ -688,7 +725,7 @@
 //! }
 //! 
 //! impl Screen<ScreenID> for TickBasedScreen {
-//!     fn draw(&mut self, frame: &mut Frame) {
+//!     fn draw_with_context(&mut self, frame: &mut Frame, _context: DrawContext<ScreenID>) {
 //!         let text = Text::from(format!(
 //!             "{} Dummy loading... (press Q to exit)",
 //!             get_tick(self.tick)
@@ -734,20 +771,55 @@
 //! hold of [`StateHandle`]s across `await` points to avoid deadlocks. Instead, clone the [`State`]
 //! and get a new handle when needed.
 //!
+//! If you do need to hold the state across `await` points, use [`AsyncState`] instead. It's backed
+//! by tokio's [`RwLock`](tokio::sync::RwLock), so waiting for it doesn't block the thread and
+//! readers don't block each other, at the cost of having to `.await` every access. Use it as the
+//! application state and implement [`ScreenWithState<ID, AsyncState<T>>`](ScreenWithState) for
+//! your screens, just like with [`State`].
+//!
+//! To wake background tasks up only when the state changes, use [`WatchableState`]. Every write
+//! through [`WatchableState::get_mut()`] notifies the receivers returned by
+//! [`WatchableState::watch()`], so tasks don't need to poll the state on a timer.
+//!
 //! # Contributing
 //!
 //! `ratapp` is pretty new, so some things may be undocumented or missing. If you find any of that,
 //! feel free to open an issue or PR in our [GitHub repository](https://github.com/Nekidev/ratapp).
 //! All contributions are welcome!
 
+#![allow(async_fn_in_trait)]
+
 mod app;
+pub mod builder;
+mod event_source;
+pub mod ext;
+pub mod filter;
+mod help;
+mod input;
 mod navigation;
+#[cfg(feature = "persistence")]
+mod persistence;
+pub mod prelude;
 mod screen;
 mod state;
 
-pub use app::App;
-pub use navigation::Navigator;
-pub use screen::{Screen, ScreenState, ScreenWithState};
-pub use state::{State, StateHandle};
+pub use app::{
+    App, DrawErrorPolicy, EventPriority, Handlers, HandlersFor, HookErrorAction, NoHandlers,
+    RenderMetrics, StackOverflowPolicy,
+};
+pub use event_source::{CrosstermEventSource, EventSource};
+pub use input::InputEvent;
+pub use navigation::{
+    Action, FocusTransferPolicy, Navigator, NavigatorMiddleware, PeekableNavigator, PostActionHook,
+    TransactionBuilder,
+};
+pub use screen::{
+    DrawContext, FocusState, HookError, HookResult, ResumeReason, Screen, ScreenState,
+    ScreenWithState,
+};
+pub use state::{
+    AsyncState, AsyncStateReadHandle, AsyncStateWriteHandle, State, StateHandle, WatchableState,
+    WatchableStateHandle, WatchableStateReadHandle,
+};
 
 pub use ratapp_macros::Screens;
@@ -5,37 +5,493 @@
 //!
 //! Check out the documentation of the [`Navigator`] for more information.
 
-use tokio::sync::mpsc;
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::AbortHandle,
+    time,
+};
+
+use crate::app::RenderMetrics;
 
 /// Allows screens to navigate between each other, request re-draws, or exit the application.
 ///
 /// The API has a few methods to perform navigation actions:
 /// - [`Navigator::push()`]: Pushes a new screen onto the navigation stack.
+/// - [`Navigator::push_with_callback()`]: Pushes a new screen, calling a function once it's done.
+/// - [`Navigator::push_with_data()`]: Pushes a new screen, along with some data for it.
+/// - [`Navigator::push_overlay()`]: Pushes a new screen, drawing it on top of the current one.
+/// - [`Navigator::push_unique()`]: Pushes a new screen, unless it's already the current one.
 /// - [`Navigator::replace()`]: Replaces the current screen with a new one.
+/// - [`Navigator::replace_with_focus()`]: Replaces the current screen, carrying its focus over.
+/// - [`Navigator::replace_root()`]: Replaces the bottom-most screen of the navigation stack.
 /// - [`Navigator::back()`]: Pops the current screen off the navigation stack, returning to the
 ///   previous screen.
+/// - [`Navigator::back_with_data()`]: Pops the current screen off the navigation stack, sending
+///   some data to the previous screen.
+/// - [`Navigator::forward()`]: Pushes back the last screen popped with [`Navigator::back()`].
+/// - [`Navigator::clear_forward()`]: Discards the screens [`Navigator::forward()`] would push.
+/// - [`Navigator::pop_to()`]: Pops screens off the navigation stack until the given screen is on
+///   top.
+/// - [`Navigator::pop_all_except_root()`]: Pops every screen off the navigation stack except the
+///   bottom-most one.
 /// - [`Navigator::clear()`]: Clears the entire navigation stack, leaving only the current screen.
 /// - [`Navigator::restart()`]: Restarts the application, clearing the navigation stack and
 ///   returning to the initial screen.
+/// - [`Navigator::replace_history()`]: Replaces the whole navigation stack with new screens.
 /// - [`Navigator::exit()`]: Exits the application.
 /// - [`Navigator::redraw()`]: Requests a re-draw of the current screen.
+/// - [`Navigator::redraw_after()`]: Requests a re-draw of the current screen after a delay.
+/// - [`Navigator::debounce_redraw()`]: Requests a re-draw, coalescing the requests made in quick
+///   succession.
+/// - [`Navigator::set_terminal_title()`]: Sets the title of the terminal window.
+/// - [`Navigator::transaction()`]: Queues several actions to be processed together.
+/// - [`Navigator::spawn_managed()`]: Spawns a background task that's cancelled when the current
+///   screen exits.
+/// - [`Navigator::send_event()`]: Sends a custom event to the current screen.
+///
+/// It also lets screens inspect the navigation stack:
+/// - [`Navigator::stack_depth()`]: Returns the number of screens in the navigation stack.
+/// - [`Navigator::can_go_back()`]: Returns whether there's a previous screen to go back to.
+/// - [`Navigator::can_go_forward()`]: Returns whether there's a screen to go forward to.
+/// - [`Navigator::history()`]: Returns the IDs of the screens in the navigation stack.
+/// - [`Navigator::current_id()`]: Returns the ID of the current screen.
+/// - [`Navigator::current_screen_name()`] and [`Navigator::current_screen_description()`]:
+///   Return the metadata of the current screen.
+/// - [`Navigator::get_screen_data()`]: Returns the data the current screen was pushed with.
+/// - [`Navigator::iteration_count()`]: Returns how many iterations the event loop has gone
+///   through.
+/// - [`Navigator::render_metrics()`]: Returns statistics about the frames drawn so far.
+/// - [`Navigator::is_cancelled()`]: Returns whether a newer event arrived while the current one
+///   is being handled.
+/// - [`Navigator::peekable()`]: Returns a read-only view of the navigation stack.
+///
+/// And observe the actions processed by the application:
+/// - [`Navigator::on_action_processed()`]: Registers a hook called after each processed action.
+///
+/// The `E` parameter is the type of the custom events sent with [`Navigator::send_event()`],
+/// the one set with [`App::with_custom_events()`](crate::App::with_custom_events). It's `()` by
+/// default, so screens without custom events can write `Navigator<ScreenID>`.
 ///
 /// [`Navigator`]s are clonable and sendable, so you can
-#[derive(Clone)]
-pub struct Navigator<ID> {
-    pub(crate) channel: mpsc::UnboundedSender<Action<ID>>,
+pub struct Navigator<ID, E = ()> {
+    pub(crate) channel: mpsc::UnboundedSender<Command<ID>>,
+    shared: Arc<Shared<ID>>,
+    /// The sender of the custom events handled by the screens' `on_custom_event` hook.
+    custom_events: mpsc::UnboundedSender<E>,
+}
+
+/// State shared between all clones of a [`Navigator`].
+struct Shared<ID> {
+    stack_depth: AtomicUsize,
+    /// The number of screens [`Navigator::forward()`] can push back.
+    forward_depth: AtomicUsize,
+    iteration_count: AtomicU64,
+    /// Whether a newer event arrived while `Screen::on_event` is handling one.
+    event_cancelled: AtomicBool,
+    /// The IDs of the screens in the navigation stack, from the bottom to the current screen.
+    stack: Mutex<Vec<ID>>,
+    /// Taken by the first [`Navigator::exit()`] call, so the exit is only requested once.
+    exit: Mutex<Option<oneshot::Sender<()>>>,
+    post_action_hooks: Mutex<Vec<PostActionHook<ID>>>,
+    /// The managed tasks of the current screen, which [`Navigator::spawn_managed()`] adds to.
+    tasks: Mutex<ManagedTasks>,
+    /// The data the current screen was pushed with, returned by [`Navigator::get_screen_data()`].
+    screen_data: Mutex<Option<ScreenData>>,
+    /// The name and description of the current screen.
+    screen_info: Mutex<Option<(&'static str, &'static str)>>,
+    render_metrics: Mutex<RenderMetrics>,
+    redraw_debounce: Mutex<RedrawDebounce>,
+}
+
+/// The state of [`Navigator::debounce_redraw()`].
+#[derive(Default)]
+struct RedrawDebounce {
+    /// When the current debounce window ends, if one was opened.
+    window_end: Option<time::Instant>,
+    /// Whether a re-draw is scheduled for the end of the current window.
+    scheduled: bool,
+}
+
+/// The data a screen was pushed with by [`Navigator::push_with_data()`].
+pub(crate) type ScreenData = Arc<dyn Any + Send + Sync>;
+
+/// A hook called by the application loop after a navigation action has been processed.
+///
+/// Check out [`Navigator::with_post_action_hook()`] for more information.
+pub type PostActionHook<ID> = Arc<dyn Fn(&Action<ID>) + Send + Sync>;
+
+/// Intercepts navigation actions before the application loop processes them.
+///
+/// Middlewares are added with [`App::with_middleware()`](crate::App::with_middleware), and are
+/// called in the order they were added, each one with the action returned by the previous one.
+/// They're called with each action of a [`transaction`](Navigator::transaction) separately, and
+/// aren't called for [`Action::Exit`], which doesn't go through the actions channel.
+///
+/// Closures taking an action and returning an optional one implement this trait, so simple
+/// middlewares don't need a type of their own.
+pub trait NavigatorMiddleware<ID> {
+    /// Intercepts an action before it's processed.
+    ///
+    /// Returns `None` to drop the action, which is then neither processed nor passed to the
+    /// following middlewares and post-action hooks, or `Some` with the action to process instead,
+    /// usually a clone of the given one. Returning an [`Action::Batch`] isn't supported, the batch
+    /// is ignored.
+    ///
+    /// Arguments:
+    /// * `action` - The action about to be processed.
+    fn intercept(&mut self, action: &Action<ID>) -> Option<Action<ID>>;
+}
+
+impl<ID, F> NavigatorMiddleware<ID> for F
+where
+    F: FnMut(&Action<ID>) -> Option<Action<ID>>,
+{
+    fn intercept(&mut self, action: &Action<ID>) -> Option<Action<ID>> {
+        self(action)
+    }
+}
+
+// Implemented manually so that cloning a `Navigator` doesn't require `ID: Clone` nor `E: Clone`.
+impl<ID, E> Clone for Navigator<ID, E> {
+    fn clone(&self) -> Self {
+        Navigator {
+            channel: self.channel.clone(),
+            shared: self.shared.clone(),
+            custom_events: self.custom_events.clone(),
+        }
+    }
 }
 
-impl<ID> Navigator<ID> {
-    pub(crate) fn new(channel: mpsc::UnboundedSender<Action<ID>>) -> Self {
-        Navigator { channel }
+impl<ID: fmt::Debug, E> fmt::Debug for Navigator<ID, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let channel = if self.channel.is_closed() {
+            "closed"
+        } else {
+            "open"
+        };
+        let stack = self
+            .shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex");
+
+        f.debug_struct("Navigator")
+            .field("channel", &format_args!("{channel}"))
+            .field("stack", &*stack)
+            .finish()
+    }
+}
+
+impl<ID, E> Navigator<ID, E> {
+    pub(crate) fn new(
+        channel: mpsc::UnboundedSender<Command<ID>>,
+        exit: oneshot::Sender<()>,
+        custom_events: mpsc::UnboundedSender<E>,
+    ) -> Self {
+        Navigator {
+            channel,
+            shared: Arc::new(Shared {
+                stack_depth: AtomicUsize::new(0),
+                forward_depth: AtomicUsize::new(0),
+                iteration_count: AtomicU64::new(0),
+                event_cancelled: AtomicBool::new(false),
+                stack: Mutex::new(Vec::new()),
+                exit: Mutex::new(Some(exit)),
+                post_action_hooks: Mutex::new(Vec::new()),
+                tasks: Mutex::new(ManagedTasks::new()),
+                screen_data: Mutex::new(None),
+                screen_info: Mutex::new(None),
+                render_metrics: Mutex::new(RenderMetrics::default()),
+                redraw_debounce: Mutex::new(RedrawDebounce::default()),
+            }),
+            custom_events,
+        }
+    }
+
+    /// Updates the navigation stack seen by the navigator, given the IDs of its screens from the
+    /// bottom to the current screen.
+    pub(crate) fn set_stack(&self, ids: impl IntoIterator<Item = ID>) {
+        let mut stack = self
+            .shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex");
+
+        stack.clear();
+        stack.extend(ids);
+
+        self.shared
+            .stack_depth
+            .store(stack.len(), Ordering::Relaxed);
+    }
+
+    /// Sets the managed tasks of the current screen, which new managed tasks are added to.
+    pub(crate) fn set_current_tasks(&self, tasks: ManagedTasks) {
+        *self
+            .shared
+            .tasks
+            .lock()
+            .expect("Failed to lock the managed tasks mutex") = tasks;
+    }
+
+    /// Sets the data returned by [`Navigator::get_screen_data()`].
+    pub(crate) fn set_screen_data(&self, data: Option<ScreenData>) {
+        *self
+            .shared
+            .screen_data
+            .lock()
+            .expect("Failed to lock the screen data mutex") = data;
+    }
+
+    /// Sets the name and description returned by [`Navigator::current_screen_name()`] and
+    /// [`Navigator::current_screen_description()`].
+    pub(crate) fn set_screen_info(&self, info: Option<(&'static str, &'static str)>) {
+        *self
+            .shared
+            .screen_info
+            .lock()
+            .expect("Failed to lock the screen info mutex") = info;
+    }
+
+    /// Sets the metrics returned by [`Navigator::render_metrics()`].
+    pub(crate) fn set_render_metrics(&self, metrics: RenderMetrics) {
+        *self
+            .shared
+            .render_metrics
+            .lock()
+            .expect("Failed to lock the render metrics mutex") = metrics;
+    }
+
+    /// Increments the counter reported by [`Navigator::iteration_count()`].
+    pub(crate) fn increment_iteration_count(&self) {
+        self.shared.iteration_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Updates the number of screens [`Navigator::forward()`] can push back.
+    pub(crate) fn set_forward_depth(&self, depth: usize) {
+        self.shared.forward_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Sets whether the event being handled by the current screen was superseded by a newer one.
+    pub(crate) fn set_event_cancelled(&self, cancelled: bool) {
+        self.shared
+            .event_cancelled
+            .store(cancelled, Ordering::Relaxed);
+    }
+
+    /// Calls every registered post-action hook with the given action.
+    pub(crate) fn run_post_action_hooks(&self, action: &Action<ID>) {
+        // The hooks are cloned out of the lock so they can register other hooks.
+        let hooks = self
+            .shared
+            .post_action_hooks
+            .lock()
+            .expect("Failed to lock the post-action hooks mutex")
+            .clone();
+
+        for hook in hooks {
+            hook(action);
+        }
+    }
+
+    /// Returns a read-only view of the navigation stack.
+    ///
+    /// Check out [`PeekableNavigator`] for more information.
+    pub fn peekable(&self) -> PeekableNavigator<ID> {
+        PeekableNavigator {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Returns the number of screens in the navigation stack, including the current one.
+    ///
+    /// The value is kept up to date by the application loop whenever the stack changes, so it
+    /// doesn't reflect navigation actions that haven't been processed yet.
+    pub fn stack_depth(&self) -> usize {
+        self.shared.stack_depth.load(Ordering::Relaxed)
+    }
+
+    /// Returns the IDs of the screens in the navigation stack, from the bottom-most one to the
+    /// current screen.
+    ///
+    /// The IDs are a snapshot copied out of the navigator, so no lock is held once it returns,
+    /// which makes it cheap enough to render breadcrumbs in `Screen::draw_with_context`. Like
+    /// [`Navigator::stack_depth()`], it doesn't reflect navigation actions that haven't been
+    /// processed yet.
+    pub fn history(&self) -> Vec<ID>
+    where
+        ID: Clone,
+    {
+        self.shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex")
+            .clone()
+    }
+
+    /// Returns the ID of the current screen, or `None` before the first screen is entered and
+    /// after the last one exits.
+    ///
+    /// Screens can use it to tell which variant they're running as, for analytics or conditional
+    /// rendering. Like [`Navigator::stack_depth()`], it doesn't reflect navigation actions that
+    /// haven't been processed yet.
+    pub fn current_id(&self) -> Option<ID>
+    where
+        ID: Clone,
+    {
+        self.shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex")
+            .last()
+            .cloned()
+    }
+
+    /// Returns the name of the current screen, set with `Screen::NAME`, or `None` before the
+    /// first screen is entered and after the last one exits.
+    ///
+    /// Useful for status bars and debug overlays. Like [`Navigator::current_id()`], it doesn't
+    /// reflect navigation actions that haven't been processed yet.
+    pub fn current_screen_name(&self) -> Option<&'static str> {
+        self.shared
+            .screen_info
+            .lock()
+            .expect("Failed to lock the screen info mutex")
+            .map(|(name, _)| name)
+    }
+
+    /// Returns the description of the current screen, set with `Screen::DESCRIPTION`, or `None`
+    /// before the first screen is entered and after the last one exits.
+    ///
+    /// Like [`Navigator::current_id()`], it doesn't reflect navigation actions that haven't been
+    /// processed yet.
+    pub fn current_screen_description(&self) -> Option<&'static str> {
+        self.shared
+            .screen_info
+            .lock()
+            .expect("Failed to lock the screen info mutex")
+            .map(|(_, description)| description)
+    }
+
+    /// Returns the data the current screen was pushed with by [`Navigator::push_with_data()`].
+    ///
+    /// Returns `None` if the current screen was pushed without data, or if the data isn't a `D`.
+    /// Like [`Navigator::current_id()`], it doesn't reflect navigation actions that haven't been
+    /// processed yet.
+    pub fn get_screen_data<D: Any + Send + Sync>(&self) -> Option<Arc<D>> {
+        self.shared
+            .screen_data
+            .lock()
+            .expect("Failed to lock the screen data mutex")
+            .clone()?
+            .downcast()
+            .ok()
+    }
+
+    /// Returns whether [`Navigator::back()`] would return to a previous screen, i.e. whether
+    /// there's more than one screen in the navigation stack.
+    ///
+    /// Like [`Navigator::stack_depth()`], it doesn't reflect navigation actions that haven't been
+    /// processed yet.
+    pub fn can_go_back(&self) -> bool {
+        self.stack_depth() > 1
+    }
+
+    /// Returns whether [`Navigator::forward()`] would push back a screen, i.e. whether a screen
+    /// was popped with [`Navigator::back()`] since the last navigation.
+    ///
+    /// Like [`Navigator::stack_depth()`], it doesn't reflect navigation actions that haven't been
+    /// processed yet.
+    pub fn can_go_forward(&self) -> bool {
+        self.shared.forward_depth.load(Ordering::Relaxed) > 0
+    }
+
+    /// Returns how many iterations the application's event loop has gone through.
+    ///
+    /// The counter increases once per iteration, whether it handled an event, a navigation action,
+    /// or the screen's task finishing, and regardless of whether the screen was re-drawn. It's
+    /// useful for rate-limiting, debugging busy loops, and testing.
+    pub fn iteration_count(&self) -> u64 {
+        self.shared.iteration_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns statistics about the frames the application has drawn so far.
+    ///
+    /// Screens can show them in a debug mode to tune their drawing performance. They're updated
+    /// right after each frame is drawn, so they're always one frame behind while drawing. Check out
+    /// [`App::with_metrics_reporter()`](crate::App::with_metrics_reporter) to get them
+    /// periodically instead.
+    pub fn render_metrics(&self) -> RenderMetrics {
+        *self
+            .shared
+            .render_metrics
+            .lock()
+            .expect("Failed to lock the render metrics mutex")
+    }
+
+    /// Returns whether a newer event arrived while the current screen's `on_event` hook is
+    /// handling one.
+    ///
+    /// Events are handled one at a time, so a slow `on_event` delays the ones after it. Screens
+    /// doing slow work in `on_event`, like searching as the user types, can check this
+    /// periodically and return early once the event they're handling is outdated. The newer
+    /// event is handled right after `on_event` returns. Any event cancels the current one,
+    /// including mouse moves when mouse support is enabled.
+    ///
+    /// It's always `false` outside of `on_event`.
+    pub fn is_cancelled(&self) -> bool {
+        self.shared.event_cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Registers a hook that is called after each navigation action is processed.
+    ///
+    /// Hooks are called by the application loop once the action has been fully applied, i.e.
+    /// after the `Screen::on_enter`, `Screen::on_exit`, and similar hooks it triggers have
+    /// returned. This allows "after push, do X" patterns without polling.
+    ///
+    /// Hooks are shared between all clones of the [`Navigator`] and are never unregistered, so
+    /// keep them cheap. Multiple hooks can be registered, and they're called in registration
+    /// order.
+    ///
+    /// Arguments:
+    /// * `hook` - The function to call with each processed action.
+    pub fn on_action_processed(&self, hook: impl Fn(&Action<ID>) + Send + Sync + 'static) {
+        self.with_post_action_hook(Arc::new(hook));
+    }
+
+    /// Registers an already shared post-action hook.
+    ///
+    /// This is the same as [`Navigator::on_action_processed()`], but takes a [`PostActionHook`]
+    /// so the same hook can be registered on several navigators.
+    ///
+    /// Arguments:
+    /// * `hook` - The hook to call with each processed action.
+    pub fn with_post_action_hook(&self, hook: PostActionHook<ID>) {
+        self.shared
+            .post_action_hooks
+            .lock()
+            .expect("Failed to lock the post-action hooks mutex")
+            .push(hook);
     }
 
     /// Pushes a new screen onto the navigation stack.
     ///
     /// The current screen's state is preserved, and the new screen is drawn on top of it.
     /// `Screen::on_pause` will be called on the current screen, and `Screen::on_enter` will be
-    /// called on the new screen.
+    /// called on the new screen. The push is dropped if `Screen::can_leave` on the current screen
+    /// or `Screen::can_enter` on the new screen returns `false`.
     ///
     /// This method triggers a re-draw of the new screen.
     ///
@@ -43,7 +499,80 @@ impl<ID> Navigator<ID> {
     /// * `id` - The ID of the screen to push onto the stack.
     pub fn push(&self, id: ID) {
         self.channel
-            .send(Action::Push(id))
+            .send(Action::Push(id).into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pushes a new screen onto the navigation stack, calling `on_complete` once it's done.
+    ///
+    /// Behaves like [`Navigator::push()`]. `on_complete` is called by the application loop after
+    /// the push has been processed, i.e. after `Screen::on_enter` has been called on the new
+    /// screen and the post-action hooks have run. It's never called if the application exits
+    /// before processing the push.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to push onto the stack.
+    /// * `on_complete` - The function to call once the push has been processed.
+    pub fn push_with_callback(&self, id: ID, on_complete: impl FnOnce() + Send + 'static) {
+        self.channel
+            .send(Command {
+                action: Action::Push(id),
+                on_complete: Some(Box::new(on_complete)),
+                data: None,
+            })
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pushes a new screen onto the navigation stack, along with some data for it.
+    ///
+    /// Behaves like [`Navigator::push()`], but the new screen can get the data with
+    /// [`Navigator::get_screen_data()`], from `Screen::on_enter` on. It's useful to tell a detail
+    /// screen which item to show, for example. The data is kept along with the screen, and dropped
+    /// once the screen is.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to push onto the stack.
+    /// * `data` - The data to store alongside the new screen.
+    pub fn push_with_data<D: Any + Send + Sync>(&self, id: ID, data: Arc<D>) {
+        let data: ScreenData = data;
+
+        self.channel
+            .send(Command {
+                action: Action::Push(id),
+                on_complete: None,
+                data: Some(Box::new(data)),
+            })
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pushes a new screen onto the navigation stack, drawing it on top of the current one.
+    ///
+    /// Behaves like [`Navigator::push()`], but the current screen is still drawn as a backdrop
+    /// under the new one, which is useful for modals, tooltips, and popups. The current screen is
+    /// paused all the same, so it doesn't receive events until the overlay is gone. Screens can
+    /// also declare themselves as overlays with `Screen::is_overlay`.
+    ///
+    /// This method triggers a re-draw.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to push onto the stack.
+    pub fn push_overlay(&self, id: ID) {
+        self.channel
+            .send(Action::PushOverlay(id).into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pushes a new screen onto the navigation stack, unless it's already the current screen.
+    ///
+    /// Behaves like [`Navigator::push()`], but does nothing if the screen on top of the stack
+    /// already has the given ID. The check is done when the push is processed, so pressing a key
+    /// repeatedly before the first push is processed doesn't stack several copies of the screen.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to push onto the stack.
+    pub fn push_unique(&self, id: ID) {
+        self.channel
+            .send(Action::PushUnique(id).into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
 
@@ -51,7 +580,8 @@ impl<ID> Navigator<ID> {
     ///
     /// The current screen's state is discarded, and the new screen is drawn in its place.
     /// `Screen::on_exit` will be called on the current screen, and `Screen::on_enter` will be
-    /// called on the new screen.
+    /// called on the new screen. Like [`Navigator::push()`], it's dropped if the screens' guards
+    /// return `false`.
     ///
     /// This method triggers a re-draw of the new screen.
     ///
@@ -59,20 +589,137 @@ impl<ID> Navigator<ID> {
     /// * `id` - The ID of the screen to replace the current screen with.
     pub fn replace(&self, id: ID) {
         self.channel
-            .send(Action::Replace(id))
+            .send(Action::Replace(id).into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Replaces the current screen with a new one, choosing what happens to the focus.
+    ///
+    /// Behaves like [`Navigator::replace()`]. With [`FocusTransferPolicy::Preserve`], the focus is
+    /// captured with `Screen::capture_focus` on the current screen and restored with
+    /// `Screen::restore_focus` on the new one, right before it's entered. Useful to replace a form
+    /// with another version of itself, like one showing validation errors, without losing the
+    /// focused field.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to replace the current screen with.
+    /// * `policy` - Whether the new screen gets the current screen's focus.
+    pub fn replace_with_focus(&self, id: ID, policy: FocusTransferPolicy) {
+        let action = match policy {
+            FocusTransferPolicy::Reset => Action::Replace(id),
+            FocusTransferPolicy::Preserve => Action::ReplaceWithFocus(id),
+        };
+
+        self.channel
+            .send(action.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Replaces the bottom-most screen of the navigation stack with a new one.
+    ///
+    /// The screens on top of it are left untouched. `Screen::on_exit` will be called on the old
+    /// root screen, but the new one isn't visible yet, so `Screen::on_enter` is only called on it
+    /// once the user goes back to it, instead of `Screen::on_resume`. If the root screen is
+    /// the only one in the stack, this works like [`Navigator::replace()`].
+    ///
+    /// Useful to swap a login screen for the home screen once the user logs in, for example.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to replace the root screen with.
+    pub fn replace_root(&self, id: ID) {
+        self.channel
+            .send(Action::ReplaceRoot(id).into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
 
     /// Pops the current screen off the navigation stack, returning to the previous screen.
     ///
-    /// The current screen's state is discarded, and the previous screen is drawn.
-    /// `Screen::on_exit` will be called on the current screen, and `Screen::on_resume` will be
-    /// called on the previous screen.
+    /// The current screen is kept for [`Navigator::forward()`] to push it back, and the previous
+    /// screen is drawn. `Screen::on_exit` will be called on the current screen, and
+    /// `Screen::on_resume` will be called on the previous screen. Like [`Navigator::push()`], it's
+    /// dropped if the screens' guards return `false`.
     ///
     /// This method triggers a re-draw of the previous screen.
     pub fn back(&self) {
         self.channel
-            .send(Action::Back)
+            .send(Action::Back.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pops the current screen off the navigation stack, sending some data to the previous screen.
+    ///
+    /// Behaves like [`Navigator::back()`], but `Screen::on_receive` is called with the data on the
+    /// previous screen right before `Screen::on_resume`. It's useful to return a value from a
+    /// picker or a dialog. If there's no previous screen, nothing happens and the data is dropped.
+    ///
+    /// Arguments:
+    /// * `data` - The data to send to the previous screen.
+    pub fn back_with_data<D: Any + Send>(&self, data: D) {
+        self.channel
+            .send(Command {
+                action: Action::Back,
+                on_complete: None,
+                data: Some(Box::new(data)),
+            })
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pops screens off the navigation stack until the screen with the given ID is on top.
+    ///
+    /// The popped screens' states are discarded, and their `Screen::on_exit` methods are called
+    /// from the top of the stack down. `Screen::on_resume` is then called on the target screen. If
+    /// several screens in the stack have the ID, the topmost one is used.
+    ///
+    /// If no screen in the stack has the ID, or it's already the current screen, nothing happens.
+    ///
+    /// This method triggers a re-draw of the target screen.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to return to.
+    pub fn pop_to(&self, id: ID) {
+        self.channel
+            .send(Action::PopTo(id).into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pops every screen off the navigation stack except the bottom-most one, going back home.
+    ///
+    /// Unlike [`Navigator::restart()`], the bottom-most screen is kept as it was. The popped
+    /// screens' states are discarded, and their `Screen::on_exit` methods are called from the top
+    /// of the stack down. `Screen::on_resume` is then called on the bottom-most screen.
+    ///
+    /// If the current screen is the only one in the stack, nothing happens.
+    ///
+    /// This method triggers a re-draw of the bottom-most screen.
+    pub fn pop_all_except_root(&self) {
+        self.channel
+            .send(Action::PopToRoot.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Pushes back the last screen popped with [`Navigator::back()`], like a browser's forward
+    /// button.
+    ///
+    /// The screen is pushed back as it was when it was popped, and entered again: its
+    /// `Screen::on_enter` method is called, and `Screen::on_pause` is called on the current
+    /// screen. Every navigation other than going back and forward discards the screens to go
+    /// forward to. Nothing happens if there's no screen to go forward to, and like
+    /// [`Navigator::push()`], it's dropped if the screens' guards return `false`.
+    ///
+    /// This method triggers a re-draw of the screen.
+    pub fn forward(&self) {
+        self.channel
+            .send(Action::Forward.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Discards the screens [`Navigator::forward()`] would push back.
+    ///
+    /// Useful when going forward no longer makes sense, like after the data shown by the popped
+    /// screens was deleted.
+    pub fn clear_forward(&self) {
+        self.channel
+            .send(Action::ClearForward.into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
 
@@ -81,27 +728,63 @@ impl<ID> Navigator<ID> {
     /// All previous screens' states are discarded, and their `Screen::on_exit` methods are called.
     pub fn clear(&self) {
         self.channel
-            .send(Action::Clear)
+            .send(Action::Clear.into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
 
     /// Restarts the application, clearing the navigation stack and returning to the initial
     /// screen.
     ///
-    /// All screens' states are discarded, and their `Screen::on_exit` methods are called.
+    /// All screens' states are discarded, and their `Screen::on_exit` methods are called. The
+    /// initial screen is then created anew, like pushed screens are. The application state is
+    /// kept, unless it's reset with
+    /// [`App::with_state_reset_on_restart()`](crate::App::with_state_reset_on_restart) or
+    /// [`App::with_restart_state()`](crate::App::with_restart_state).
     pub fn restart(&self) {
         self.channel
-            .send(Action::Restart)
+            .send(Action::Restart.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Replaces the whole navigation stack with new screens.
+    ///
+    /// All current screens' states are discarded, and their `Screen::on_exit` methods are called
+    /// from the top to the bottom. New screens are then created for the given IDs, from the
+    /// bottom-most one to the new current screen, and only the current one has its
+    /// `Screen::on_enter` method called, since the rest aren't visible. They're entered once the
+    /// user goes back to them, instead of being resumed. Useful to restore where
+    /// the user was, like after loading the application state from disk.
+    ///
+    /// Nothing happens if `stack` is empty, since there must always be a current screen.
+    ///
+    /// This method triggers a re-draw.
+    ///
+    /// Arguments:
+    /// * `stack` - The IDs of the new screens, from the bottom of the stack to the top.
+    pub fn replace_history(&self, stack: Vec<ID>) {
+        self.channel
+            .send(Action::ReplaceHistory(stack).into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
 
     /// Exits the application.
     ///
     /// All screens' states are discarded, and their `Screen::on_exit` methods are called.
+    ///
+    /// The exit is processed before any other pending navigation action or re-draw, and only the
+    /// first call has any effect.
     pub fn exit(&self) {
-        self.channel
-            .send(Action::Exit)
-            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+        let exit = self
+            .shared
+            .exit
+            .lock()
+            .expect("Failed to lock the exit sender mutex")
+            .take();
+
+        if let Some(exit) = exit {
+            // The receiver is only dropped once the application has stopped running.
+            let _ = exit.send(());
+        }
     }
 
     /// Requests a re-draw of the current screen.
@@ -109,20 +792,468 @@ impl<ID> Navigator<ID> {
     /// Use this method when you want to update the UI without updating the history stack.
     pub fn redraw(&self) {
         self.channel
-            .send(Action::Redraw)
+            .send(Action::Redraw.into())
+            .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+    }
+
+    /// Requests a re-draw of the current screen once `delay` has passed.
+    ///
+    /// The wait happens in a managed task (see [`Navigator::spawn_managed()`]), so the re-draw is
+    /// cancelled if the current screen exits before it's due. Useful for animations that need a
+    /// single frame later on, without spawning a task manually.
+    ///
+    /// Arguments:
+    /// * `delay` - How long to wait before re-drawing.
+    pub fn redraw_after(&self, delay: Duration)
+    where
+        ID: Send + 'static,
+        E: Send + 'static,
+    {
+        let navigator = self.clone();
+
+        self.spawn_managed(async move {
+            time::sleep(delay).await;
+            navigator.redraw();
+        });
+    }
+
+    /// Requests a re-draw of the current screen, coalescing the requests made in quick
+    /// succession.
+    ///
+    /// The first call re-draws right away and opens a `window` during which further calls don't
+    /// re-draw. If any call was made during the window, a single re-draw happens once it ends, so
+    /// the latest changes are always drawn. Useful for screens with fast-changing data, where
+    /// most re-draws would be wasted.
+    ///
+    /// The re-draw at the end of the window happens in a managed task (see
+    /// [`Navigator::spawn_managed()`]), so it's skipped if the current screen exits before.
+    ///
+    /// Arguments:
+    /// * `window` - How long to coalesce re-draw requests for after a re-draw.
+    pub fn debounce_redraw(&self, window: Duration)
+    where
+        ID: Send + 'static,
+        E: Send + 'static,
+    {
+        let mut debounce = self
+            .shared
+            .redraw_debounce
+            .lock()
+            .expect("Failed to lock the redraw debounce mutex");
+        let now = time::Instant::now();
+
+        match debounce.window_end {
+            Some(window_end) if now < window_end => {
+                if !debounce.scheduled {
+                    debounce.scheduled = true;
+
+                    let navigator = self.clone();
+                    self.spawn_managed(async move {
+                        time::sleep_until(window_end).await;
+                        navigator.debounce_redraw(window);
+                    });
+                }
+            }
+            _ => {
+                debounce.window_end = Some(now + window);
+                debounce.scheduled = false;
+                self.redraw();
+            }
+        }
+    }
+
+    /// Sets the title of the terminal window.
+    ///
+    /// Screens can also set their title declaratively with `Screen::title`, which is applied
+    /// every time they become the current screen. This method sets it at any other time, like
+    /// when the context shown by the screen changes.
+    ///
+    /// Arguments:
+    /// * `title` - The new terminal title.
+    pub fn set_terminal_title(&self, title: impl Into<String>) {
+        self.channel
+            .send(Action::SetTitle(title.into()).into())
             .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
     }
+
+    /// Queues several navigation actions to be processed together.
+    ///
+    /// The actions queued on the [`TransactionBuilder`] are sent as a single batch once `f`
+    /// returns. The application loop processes them in order, without handling any event or
+    /// other action in between, so it's safe to push a screen and exit right after, for example:
+    ///
+    /// ```ignore
+    /// navigator.transaction(|transaction| {
+    ///     transaction.clear();
+    ///     transaction.push(ScreenID::Home);
+    /// });
+    /// ```
+    ///
+    /// Nothing is sent if no action was queued.
+    ///
+    /// An exit cuts the batch short: the actions queued before [`TransactionBuilder::exit()`] are
+    /// processed and reported to the post-action hooks as usual, while the ones queued after it
+    /// are dropped. The exit itself is reported to the post-action hooks once every screen has
+    /// exited.
+    ///
+    /// Arguments:
+    /// * `f` - The function queuing the actions.
+    pub fn transaction(&self, f: impl FnOnce(&TransactionBuilder<ID>)) {
+        let transaction = TransactionBuilder {
+            actions: RefCell::new(Vec::new()),
+        };
+
+        f(&transaction);
+
+        let actions = transaction.actions.into_inner();
+
+        if !actions.is_empty() {
+            self.channel
+                .send(Action::Batch(actions).into())
+                .expect("The Navigator actions channel was dropped! This is a ratapp bug.");
+        }
+    }
+
+    /// Spawns a background task that's cancelled when the current screen exits.
+    ///
+    /// The future is spawned with [`tokio::spawn()`] right away, and aborted once
+    /// `Screen::on_exit` has been called on the screen that was current when it was spawned, so
+    /// there's no need to keep its `JoinHandle` around and abort it manually. Tasks spawned after
+    /// that screen has exited are aborted immediately.
+    ///
+    /// Arguments:
+    /// * `future` - The future to run in the background.
+    pub fn spawn_managed<F>(&self, future: F)
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let handle = tokio::spawn(future);
+
+        self.shared
+            .tasks
+            .lock()
+            .expect("Failed to lock the managed tasks mutex")
+            .add(handle.abort_handle());
+    }
+
+    /// Sends a custom event to the current screen.
+    ///
+    /// The event is handled by the `Screen::on_custom_event` hook of whatever screen is current
+    /// once it's received, which makes it a good way for background tasks to report back. Events
+    /// sent after the application has stopped are discarded.
+    ///
+    /// Arguments:
+    /// * `event` - The event to send, of the type set with
+    ///   [`App::with_custom_events()`](crate::App::with_custom_events).
+    pub fn send_event(&self, event: E) {
+        // The receiver is only dropped once the application has stopped running.
+        let _ = self.custom_events.send(event);
+    }
+}
+
+/// A read-only view of the navigation stack, returned by [`Navigator::peekable()`].
+///
+/// Unlike [`Navigator`], it can't navigate, so it's what screens get in
+/// [`DrawContext::navigator`](crate::DrawContext::navigator) to render breadcrumbs and the like
+/// while drawing. Like [`Navigator`], it's clonable and sendable, and it doesn't reflect
+/// navigation actions that haven't been processed yet.
+pub struct PeekableNavigator<ID> {
+    shared: Arc<Shared<ID>>,
+}
+
+impl<ID> Clone for PeekableNavigator<ID> {
+    fn clone(&self) -> Self {
+        PeekableNavigator {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<ID: fmt::Debug> fmt::Debug for PeekableNavigator<ID> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stack = self
+            .shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex");
+
+        f.debug_struct("PeekableNavigator")
+            .field("stack", &*stack)
+            .finish()
+    }
+}
+
+impl<ID> PeekableNavigator<ID> {
+    /// Returns the number of screens in the navigation stack, including the current one.
+    pub fn depth(&self) -> usize {
+        self.shared.stack_depth.load(Ordering::Relaxed)
+    }
+
+    /// Returns the ID of the current screen, or `None` before the first screen is entered and
+    /// after the last one exits.
+    pub fn top_id(&self) -> Option<ID>
+    where
+        ID: Clone,
+    {
+        self.shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex")
+            .last()
+            .cloned()
+    }
+
+    /// Returns the IDs of the screens in the navigation stack, from the bottom-most one to the
+    /// current screen.
+    pub fn ids(&self) -> Vec<ID>
+    where
+        ID: Clone,
+    {
+        self.shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex")
+            .clone()
+    }
+
+    /// Returns whether a screen with the given ID is in the navigation stack.
+    ///
+    /// Arguments:
+    /// * `id` - The ID of the screen to look for.
+    pub fn contains(&self, id: ID) -> bool
+    where
+        ID: PartialEq,
+    {
+        self.shared
+            .stack
+            .lock()
+            .expect("Failed to lock the stack mutex")
+            .contains(&id)
+    }
+}
+
+/// Queues navigation actions for [`Navigator::transaction()`].
+///
+/// Its methods mirror the [`Navigator`] ones, but the actions are only sent once the transaction
+/// is complete. An exit stops the batch, so actions queued after [`TransactionBuilder::exit()`]
+/// are never processed.
+pub struct TransactionBuilder<ID> {
+    actions: RefCell<Vec<Action<ID>>>,
+}
+
+impl<ID> TransactionBuilder<ID> {
+    fn queue(&self, action: Action<ID>) {
+        self.actions.borrow_mut().push(action);
+    }
+
+    /// Queues a [`Navigator::push()`].
+    pub fn push(&self, id: ID) {
+        self.queue(Action::Push(id));
+    }
+
+    /// Queues a [`Navigator::push_overlay()`].
+    pub fn push_overlay(&self, id: ID) {
+        self.queue(Action::PushOverlay(id));
+    }
+
+    /// Queues a [`Navigator::push_unique()`].
+    pub fn push_unique(&self, id: ID) {
+        self.queue(Action::PushUnique(id));
+    }
+
+    /// Queues a [`Navigator::replace()`].
+    pub fn replace(&self, id: ID) {
+        self.queue(Action::Replace(id));
+    }
+
+    /// Queues a [`Navigator::replace_with_focus()`].
+    pub fn replace_with_focus(&self, id: ID, policy: FocusTransferPolicy) {
+        self.queue(match policy {
+            FocusTransferPolicy::Reset => Action::Replace(id),
+            FocusTransferPolicy::Preserve => Action::ReplaceWithFocus(id),
+        });
+    }
+
+    /// Queues a [`Navigator::replace_root()`].
+    pub fn replace_root(&self, id: ID) {
+        self.queue(Action::ReplaceRoot(id));
+    }
+
+    /// Queues a [`Navigator::back()`].
+    pub fn back(&self) {
+        self.queue(Action::Back);
+    }
+
+    /// Queues a [`Navigator::forward()`].
+    pub fn forward(&self) {
+        self.queue(Action::Forward);
+    }
+
+    /// Queues a [`Navigator::clear_forward()`].
+    pub fn clear_forward(&self) {
+        self.queue(Action::ClearForward);
+    }
+
+    /// Queues a [`Navigator::pop_to()`].
+    pub fn pop_to(&self, id: ID) {
+        self.queue(Action::PopTo(id));
+    }
+
+    /// Queues a [`Navigator::pop_all_except_root()`].
+    pub fn pop_all_except_root(&self) {
+        self.queue(Action::PopToRoot);
+    }
+
+    /// Queues a [`Navigator::clear()`].
+    pub fn clear(&self) {
+        self.queue(Action::Clear);
+    }
+
+    /// Queues a [`Navigator::restart()`].
+    pub fn restart(&self) {
+        self.queue(Action::Restart);
+    }
+
+    /// Queues a [`Navigator::replace_history()`].
+    pub fn replace_history(&self, stack: Vec<ID>) {
+        self.queue(Action::ReplaceHistory(stack));
+    }
+
+    /// Queues a [`Navigator::exit()`], dropping the actions queued after it.
+    pub fn exit(&self) {
+        self.queue(Action::Exit);
+    }
+
+    /// Queues a [`Navigator::redraw()`].
+    pub fn redraw(&self) {
+        self.queue(Action::Redraw);
+    }
+
+    /// Queues a [`Navigator::set_terminal_title()`].
+    pub fn set_terminal_title(&self, title: impl Into<String>) {
+        self.queue(Action::SetTitle(title.into()));
+    }
+}
+
+/// The background tasks spawned with [`Navigator::spawn_managed()`] for a screen.
+///
+/// It's kept by the application loop alongside its screen, and aborted once the screen exits.
+#[derive(Clone)]
+pub(crate) struct ManagedTasks(Arc<Mutex<Option<Vec<AbortHandle>>>>);
+
+impl ManagedTasks {
+    pub(crate) fn new() -> Self {
+        ManagedTasks(Arc::new(Mutex::new(Some(Vec::new()))))
+    }
+
+    /// Adds a task to the set, aborting it right away if the set was already aborted.
+    fn add(&self, task: AbortHandle) {
+        let mut tasks = self
+            .0
+            .lock()
+            .expect("Failed to lock the managed tasks mutex");
+
+        match tasks.as_mut() {
+            Some(tasks) => {
+                tasks.retain(|task| !task.is_finished());
+                tasks.push(task);
+            }
+            None => task.abort(),
+        }
+    }
+
+    /// Aborts every task in the set, and any task added to it afterwards.
+    pub(crate) fn abort_all(&self) {
+        let tasks = self
+            .0
+            .lock()
+            .expect("Failed to lock the managed tasks mutex")
+            .take();
+
+        for task in tasks.into_iter().flatten() {
+            task.abort();
+        }
+    }
 }
 
 /// Actions that can be performed by the [`Navigator`].
 ///
-/// These actions are sent to the main application loop to be processed.
-pub(crate) enum Action<ID> {
+/// These actions are sent to the main application loop to be processed. They're exposed so that
+/// post-action hooks can tell which action was processed, and so that [`NavigatorMiddleware`]s can
+/// inspect and rewrite them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Action<ID> {
+    /// Sent by [`Navigator::push()`].
     Push(ID),
+    /// Sent by [`Navigator::push_overlay()`].
+    PushOverlay(ID),
+    /// Sent by [`Navigator::push_unique()`].
+    PushUnique(ID),
+    /// Sent by [`Navigator::replace()`], and by [`Navigator::replace_with_focus()`] with
+    /// [`FocusTransferPolicy::Reset`].
     Replace(ID),
+    /// Sent by [`Navigator::replace_with_focus()`] with [`FocusTransferPolicy::Preserve`].
+    ReplaceWithFocus(ID),
+    /// Sent by [`Navigator::replace_root()`].
+    ReplaceRoot(ID),
+    /// Sent by [`Navigator::back()`].
     Back,
+    /// Sent by [`Navigator::forward()`].
+    Forward,
+    /// Sent by [`Navigator::clear_forward()`].
+    ClearForward,
+    /// Sent by [`Navigator::pop_to()`].
+    PopTo(ID),
+    /// Sent by [`Navigator::pop_all_except_root()`].
+    PopToRoot,
+    /// Sent by [`Navigator::clear()`].
     Clear,
+    /// Sent by [`Navigator::restart()`].
     Restart,
+    /// Sent by [`Navigator::replace_history()`].
+    ReplaceHistory(Vec<ID>),
+    /// Requested by [`Navigator::exit()`]. Exits don't go through the actions channel, but
+    /// post-action hooks are still called with this action once every screen has exited.
     Exit,
+    /// Sent by [`Navigator::redraw()`].
     Redraw,
+    /// Sent by [`Navigator::set_terminal_title()`].
+    SetTitle(String),
+    /// Sent by [`Navigator::transaction()`]. Its actions are processed in order, one right after
+    /// the other, and post-action hooks are called with each of them instead of the batch.
+    Batch(Vec<Action<ID>>),
+}
+
+/// What [`Navigator::replace_with_focus()`] does with the focus of the replaced screen.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum FocusTransferPolicy {
+    /// The new screen starts with its own focus, like with [`Navigator::replace()`].
+    #[default]
+    Reset,
+
+    /// The new screen gets the focus of the replaced one, through `Screen::capture_focus` and
+    /// `Screen::restore_focus`.
+    Preserve,
+}
+
+/// A navigation action sent to the application loop, along with an optional callback to call once
+/// it's been processed.
+pub(crate) struct Command<ID> {
+    pub(crate) action: Action<ID>,
+    pub(crate) on_complete: Option<Box<dyn FnOnce() + Send>>,
+    /// The data sent with [`Navigator::back_with_data()`], or a [`ScreenData`] sent with
+    /// [`Navigator::push_with_data()`].
+    pub(crate) data: Option<Box<dyn Any + Send>>,
+}
+
+impl<ID> From<Action<ID>> for Command<ID> {
+    fn from(action: Action<ID>) -> Self {
+        Command {
+            action,
+            on_complete: None,
+            data: None,
+        }
+    }
 }
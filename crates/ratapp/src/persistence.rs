@@ -0,0 +1,59 @@
+//! Saving and loading the application state to and from disk.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Where and how often the application state is saved, set with [`App::with_state_snapshot()`].
+///
+/// [`App::with_state_snapshot()`]: crate::App::with_state_snapshot
+pub(crate) struct StateSnapshot<T> {
+    pub(crate) path: PathBuf,
+    pub(crate) interval: Duration,
+    /// Serializes the state. It's stored here so that `App::run()` doesn't need to require
+    /// `T: Serialize`.
+    serialize: fn(&T) -> serde_json::Result<Vec<u8>>,
+}
+
+impl<T> StateSnapshot<T> {
+    pub(crate) fn new(path: PathBuf, interval: Duration) -> Self
+    where
+        T: Serialize,
+    {
+        StateSnapshot {
+            path,
+            interval,
+            serialize: serde_json::to_vec::<T>,
+        }
+    }
+
+    /// Serializes the state, to be written with [`write()`].
+    pub(crate) fn serialize(&self, state: &T) -> io::Result<Vec<u8>> {
+        Ok((self.serialize)(state)?)
+    }
+}
+
+/// Writes a serialized state to `path`.
+///
+/// The data is written to a temporary file first and then moved into place, so a crash while
+/// writing never leaves a half-written snapshot behind.
+pub(crate) fn write(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Reads the state saved at `path`, if there's any and it's valid.
+pub(crate) fn load<T>(path: &Path) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let data = fs::read(path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
@@ -0,0 +1,10 @@
+//! Re-exports of the most commonly used ratapp items.
+//!
+//! ```ignore
+//! use ratapp::prelude::*;
+//! ```
+
+pub use crate::{
+    App, AsyncState, HookResult, InputEvent, Navigator, ResumeReason, Screen, ScreenState,
+    ScreenWithState, Screens, State, builder::AppBuilder, ext::NavigatorExt,
+};
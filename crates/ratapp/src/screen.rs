@@ -1,8 +1,15 @@
-use std::future;
+use std::{any::Any, error::Error, fmt::Debug, future, time::Duration};
 
-use ratatui::{Frame, crossterm::event::Event};
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+    layout::Rect,
+};
 
-use crate::navigation::Navigator;
+use crate::{
+    input::InputEvent,
+    navigation::{Navigator, PeekableNavigator},
+};
 
 /// The state of the application screen.
 ///
@@ -23,14 +30,14 @@ use crate::navigation::Navigator;
 ///         }
 ///     }
 ///
-///     fn draw(&mut self, frame: &mut Frame) {
+///     fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<Self::ID>) {
 ///         match self {
-///             ScreenID::First => self.first.draw(frame),
-///             ScreenID::Second => self.second.draw(frame),
+///             ScreenID::First => self.first.draw_with_context(frame, context),
+///             ScreenID::Second => self.second.draw_with_context(frame, context),
 ///         }
 ///     }
 ///
-///     async fn on_event(&mut self, event: Event, navigator: &Navigator<Self::ID>) {
+///     async fn on_event(&mut self, event: &Event, navigator: &Navigator<Self::ID>) -> HookResult {
 ///         match self {
 ///             ScreenID::First => self.first.on_event(event, navigator).await,
 ///             ScreenID::Second => self.second.on_event(event, navigator).await,
@@ -45,6 +52,7 @@ use crate::navigation::Navigator;
 /// so:
 ///
 /// ```rust
+/// #[derive(Debug, Clone, Copy, PartialEq)]
 /// enum ScreenID {
 ///     First,
 ///     Second,
@@ -53,54 +61,566 @@ use crate::navigation::Navigator;
 ///
 /// And that's it! You can now use your `ScreenState` implementation with the [`App`](crate::App)
 /// struct to run your application.
-pub trait ScreenState<S = ()>: Default {
-    type ID: Copy;
+#[allow(unused_variables)]
+pub trait ScreenState<S = (), E = ()>: Default {
+    /// The type identifying the screens, like the `ScreenID` enum generated by
+    /// [`Screens`](crate::Screens).
+    ///
+    /// It must be `Copy`, `Debug`, `PartialEq` and `'static`. The generated enums are, but this is
+    /// a breaking change for manual implementations: earlier versions only required `Copy`, so
+    /// their ID types may need `#[derive(Debug, PartialEq)]`. `Debug` is used to log and report
+    /// the screens, and `PartialEq` to find them in the navigation stack.
+    type ID: Copy + Debug + PartialEq + 'static;
 
     fn new(id: Self::ID) -> Self;
-    fn draw(&mut self, frame: &mut Frame, state: &S);
-    async fn on_event(&mut self, event: Event, navigator: Navigator<Self::ID>, state: &mut S);
-    async fn on_enter(&mut self, navigator: Navigator<Self::ID>, state: &mut S);
-    async fn on_exit(&mut self, navigator: Navigator<Self::ID>, state: &mut S);
-    async fn on_pause(&mut self, navigator: Navigator<Self::ID>, state: &mut S);
-    async fn on_resume(&mut self, navigator: Navigator<Self::ID>, state: &mut S);
-    async fn task(&mut self, navigator: Navigator<Self::ID>, state: &mut S);
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn try_new(id: Self::ID) -> Option<Self> {
+        Some(Self::new(id))
+    }
+    fn id(&self) -> Self::ID;
+    fn preferred_area(&self, full_area: Rect, state: &S) -> Rect;
+    #[deprecated(note = "implement `ScreenState::draw_with_context()` instead")]
+    fn draw(&mut self, frame: &mut Frame, state: &S) {}
+    fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<Self::ID>, state: &S) {
+        #[allow(deprecated)]
+        self.draw(frame, state);
+    }
+    async fn on_event(
+        &mut self,
+        event: &Event,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_resize(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_focus_gained(
+        &mut self,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_focus_lost(
+        &mut self,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_paste(
+        &mut self,
+        content: String,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_event_timeout(
+        &mut self,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn can_leave(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S) -> bool;
+    async fn can_enter(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S) -> bool;
+    fn title(&self, state: &S) -> Option<String>;
+    fn is_overlay(&self, state: &S) -> bool;
+    async fn on_custom_event(
+        &mut self,
+        event: E,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    fn tick_interval(&self, state: &S) -> Option<Duration>;
+    async fn on_tick(
+        &mut self,
+        tick: u64,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    fn help_text(&self, state: &S) -> Vec<(KeyCode, &'static str)>;
+    fn consumes_quit_key(&self, state: &S) -> bool;
+    fn filter_event(&self, event: &Event, state: &S) -> bool;
+    fn capture_focus(&self, state: &S) -> Option<FocusState>;
+    fn restore_focus(&mut self, focus: FocusState, state: &mut S);
+    async fn on_enter(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S) -> HookResult;
+    async fn on_exit(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S) -> HookResult;
+    async fn on_pause(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S) -> HookResult;
+    async fn on_resume(
+        &mut self,
+        reason: ResumeReason<Self::ID>,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn on_receive(
+        &mut self,
+        data: Box<dyn Any + Send>,
+        navigator: Navigator<Self::ID, E>,
+        state: &mut S,
+    ) -> HookResult;
+    async fn task(&mut self, navigator: Navigator<Self::ID, E>, state: &mut S);
+}
+
+/// The error returned by a failed screen lifecycle hook.
+pub type HookError = Box<dyn Error + Send + Sync>;
+
+/// The result of a screen lifecycle hook.
+///
+/// Hooks return `Ok(())` when everything went fine. Errors are passed to the error handler set
+/// with [`App::with_error_handler()`](crate::App::with_error_handler), and by default stop the
+/// application, being returned from [`App::run()`](crate::App::run).
+pub type HookResult = Result<(), HookError>;
+
+/// Which part of a screen has the focus, captured by `Screen::capture_focus` when it's replaced
+/// with [`Navigator::replace_with_focus()`] and restored on the new screen by
+/// `Screen::restore_focus`.
+///
+/// It's type-erased so each screen can capture its focus however it sees fit, like an index or
+/// an enum of its fields.
+pub type FocusState = Box<dyn Any + Send>;
+
+/// Information about how a screen is being drawn, passed to [`Screen::draw_with_context()`] and
+/// [`ScreenWithState::draw_with_context()`].
+///
+/// It's built by the application right before drawing, so it's always up to date, unlike what a
+/// screen could keep from its lifecycle hooks.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DrawContext<ID> {
+    /// The number of screens in the navigation stack, including the current one.
+    pub stack_depth: usize,
+    /// The ID of the current screen, the one on top of the stack. Screens drawn as a backdrop
+    /// for overlays aren't the current screen.
+    pub current_id: ID,
+    /// Whether the screen is drawn on top of the screens below it, as an overlay.
+    pub is_overlay: bool,
+    /// The size of the area the application is drawn on, in columns and rows. It's the whole
+    /// terminal, or the area given to [`App::run_in_area()`](crate::App::run_in_area).
+    pub terminal_size: (u16, u16),
+    /// A read-only view of the navigation stack.
+    pub navigator: PeekableNavigator<ID>,
+}
+
+/// Why a screen was resumed (brought back to the foreground).
+///
+/// It's passed to the [`Screen::on_resume()`] and [`ScreenWithState::on_resume()`] hooks so
+/// screens can tell where they're coming back from, like the user going back as opposed to the
+/// stack being cleared programmatically. New reasons may be added in future releases, so matches
+/// on it need a wildcard arm:
+///
+/// ```ignore
+/// async fn on_resume(
+///     &mut self,
+///     reason: ResumeReason<ScreenID>,
+///     navigator: Navigator<ScreenID>,
+/// ) -> HookResult {
+///     match reason {
+///         ResumeReason::Back(ScreenID::Settings) => self.reload_settings(),
+///         ResumeReason::Cleared => self.reset(),
+///         _ => {}
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ResumeReason<ID> {
+    /// The screen on top of it was popped with [`Navigator::back()`]. Holds the ID of the popped
+    /// screen. The data it was popped with by [`Navigator::back_with_data()`], if any, is passed
+    /// to the `on_receive` hook right before.
+    Back(ID),
+    /// The screens on top of it were popped with [`Navigator::pop_all_except_root()`].
+    Cleared,
+    /// The screens on top of it were popped with [`Navigator::pop_to()`]. Holds the ID of the
+    /// screen that was on top of the stack.
+    PopTo(ID),
 }
 
 /// A screen in the application.
 ///
 /// There's a few important methods to implement:
-/// - [`draw()`](Screen::draw): Draws the screen.
+/// - [`NAME`](Screen::NAME) and [`DESCRIPTION`](Screen::DESCRIPTION): The screen's metadata,
+///   for status bars and debug overlays.
+/// - [`try_new()`](Screen::try_new): Creates the screen, if it can be.
+/// - [`draw_with_context()`](Screen::draw_with_context): Draws the screen, with information about
+///   how it's being drawn.
+/// - [`preferred_area()`](Screen::preferred_area): The area of the terminal the screen is drawn
+///   on.
 /// - [`on_event()`](Screen::on_event): Handles an event.
+/// - [`on_input()`](Screen::on_input): Handles an event, simplified into an [`InputEvent`].
+/// - [`on_resize()`](Screen::on_resize): Called when the terminal is resized.
+/// - [`on_focus_gained()`](Screen::on_focus_gained) and
+///   [`on_focus_lost()`](Screen::on_focus_lost): Called when the terminal gains or loses focus.
+/// - [`on_paste()`](Screen::on_paste): Called when text is pasted into the terminal.
+/// - [`on_event_timeout()`](Screen::on_event_timeout): Called when no event arrived for a
+///   while.
+/// - [`can_leave()`](Screen::can_leave) and [`can_enter()`](Screen::can_enter): Confirm
+///   navigating away from and to the screen.
+/// - [`title()`](Screen::title): The terminal title to set while the screen is current.
+/// - [`is_overlay()`](Screen::is_overlay): Whether the screen is drawn on top of the previous one.
+/// - [`on_custom_event()`](Screen::on_custom_event): Called when a custom event is sent with
+///   [`Navigator::send_event()`].
+/// - [`tick_interval()`](Screen::tick_interval) and [`on_tick()`](Screen::on_tick): Call the
+///   screen periodically, for animations.
+/// - [`help_text()`](Screen::help_text): The screen's key bindings, shown in the help overlay.
+/// - [`consumes_quit_key()`](Screen::consumes_quit_key): Whether the quit keys reach the screen
+///   instead of exiting the application.
+/// - [`filter_event()`](Screen::filter_event): Whether an event is passed to the screen.
+/// - [`capture_focus()`](Screen::capture_focus) and [`restore_focus()`](Screen::restore_focus):
+///   Carry the focus over to the screen replacing this one.
 /// - [`on_enter()`](Screen::on_enter): Called when the screen is entered.
 /// - [`on_pause()`](Screen::on_pause): Called when the screen is paused (sent to background).
 /// - [`on_resume()`](Screen::on_resume): Called when the screen is resumed (brought back to the
 ///   foreground).
+/// - [`on_receive()`](Screen::on_receive): Called with the data sent by a screen popped with
+///   [`Navigator::back_with_data()`].
 /// - [`on_exit()`](Screen::on_exit): Called when the screen is exited.
 /// - [`task()`](Screen::task): An asynchronous task that runs in the background while the screen
 ///   is active.
 ///
-/// All methods are asynchronous except for `draw()`.
+/// All methods are asynchronous except for `draw_with_context()`. The lifecycle hooks return a
+/// [`HookResult`], so errors can be propagated with `?`.
 ///
 /// Implementors must also implement [`Default`] to provide an initial state for the screen.
-pub trait Screen<ID>: Default {
+///
+/// Screens needing the global application state implement [`ScreenWithState`] instead. Every
+/// `Screen` is also a `ScreenWithState`, so a type can't implement both: check out
+/// [`ScreenWithState`] for how to move a screen from one to the other.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a screen with `{ID}` IDs",
+    label = "`{Self}` must implement `Screen<{ID}>` or `ScreenWithState<{ID}, _>`",
+    note = "every variant of a `#[derive(Screens)]` enum must hold a type implementing `Screen<{ID}>` or `ScreenWithState<{ID}, _>`"
+)]
+#[allow(unused_variables)]
+pub trait Screen<ID, E = ()>: Default {
+    /// The screen's name, for status bars and debug overlays.
+    ///
+    /// It's returned by [`Navigator::current_screen_name()`] while the screen is the current one.
+    /// Defaults to an empty string, in which case the name of the screen's `#[derive(Screens)]`
+    /// variant is used instead. The variant can also set it with `#[screen(name = "...")]`.
+    const NAME: &'static str = "";
+
+    /// A short description of the screen, for status bars and debug overlays.
+    ///
+    /// It's returned by [`Navigator::current_screen_description()`] while the screen is the
+    /// current one. Defaults to an empty string. The screen's `#[derive(Screens)]` variant can
+    /// also set it with `#[screen(description = "...")]`.
+    const DESCRIPTION: &'static str = "";
+
+    /// Creates the screen when it's navigated to, or returns `None` if it can't be created.
+    ///
+    /// When it returns `None`, the navigation action is dropped and the current screen stays, and
+    /// the handler set with [`App::with_navigation_error_handler()`] is called. Useful for screens
+    /// that can't be shown without something they load when created, like a file. By default, the
+    /// screen is created with [`Default`].
+    ///
+    /// [`App::with_navigation_error_handler()`]: crate::App::with_navigation_error_handler
+    fn try_new() -> Option<Self> {
+        Some(Self::default())
+    }
+
     /// Draws the screen.
     ///
+    /// Deprecated in favor of [`Screen::draw_with_context()`], which calls it by default and gets
+    /// the same frame along with how the screen is being drawn. It draws nothing by default.
+    ///
+    /// Arguments:
+    /// * `frame` - The frame to draw on.
+    #[deprecated(note = "implement `Screen::draw_with_context()` instead")]
+    fn draw(&mut self, frame: &mut Frame) {}
+
+    /// Draws the screen, with information about how it's being drawn.
+    ///
     /// Even though this method takes `&mut self`, it's usually not a good idea to modify the
     /// screen state here, as it can lead to unexpected behavior.
     ///
+    /// The context tells the screen about the navigation stack, whether it's drawn as an overlay,
+    /// and the size of the area it's drawn in. Check out [`DrawContext`] for what it holds. Screens
+    /// that don't need it can ignore it, while others can draw something about the stack, like
+    /// breadcrumbs:
+    ///
+    /// ```ignore
+    /// fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<ScreenID>) {
+    ///     let breadcrumbs = context
+    ///         .navigator
+    ///         .ids()
+    ///         .iter()
+    ///         .map(ToString::to_string)
+    ///         .collect::<Vec<_>>()
+    ///         .join(" > ");
+    ///
+    ///     frame.render_widget(Line::raw(breadcrumbs), frame.area());
+    /// }
+    /// ```
+    ///
+    /// It calls the deprecated [`Screen::draw()`] by default, so screens implementing that one
+    /// keep working.
+    ///
     /// Arguments:
     /// * `frame` - The frame to draw on.
-    fn draw(&mut self, frame: &mut Frame);
+    /// * `context` - How the screen is being drawn.
+    fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<ID>) {
+        #[allow(deprecated)]
+        self.draw(frame);
+    }
+
+    /// Returns the area of the terminal the screen wants to be drawn on.
+    ///
+    /// The returned area is the one [`Screen::draw_with_context()`] receives as `frame.area()`,
+    /// which is useful for screens that only take part of the terminal, like a centered dialog.
+    /// It's clamped to the terminal's area. By default, the screen takes the whole terminal.
+    ///
+    /// Arguments:
+    /// * `full_area` - The area of the whole terminal.
+    fn preferred_area(&self, full_area: Rect) -> Rect {
+        full_area
+    }
 
     /// Handles a terminal event.
     ///
     /// Every time an event is received, this method is called with the event and a navigator. Once
     /// it returns, the screen is re-drawn.
     ///
+    /// The event is passed by reference to avoid cloning large events like pastes. Clone it if you
+    /// need to keep it around.
+    ///
+    /// Events are handled one at a time. If handling one is slow, check
+    /// [`Navigator::is_cancelled()`] to return early once a newer event arrives.
+    ///
+    /// By default, the event is converted into an [`InputEvent`] and passed to
+    /// [`Screen::on_input()`], which is easier to implement. Override this method instead when you
+    /// need the full crossterm event.
+    ///
     /// Arguments:
     /// * `event` - The terminal event to handle.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ID>) {}
+    async fn on_event(&mut self, event: &Event, navigator: Navigator<ID, E>) -> HookResult {
+        self.on_input(event.into(), navigator).await
+    }
+
+    /// Handles a terminal event, simplified into an [`InputEvent`].
+    ///
+    /// It's called by the default [`Screen::on_event()`] implementation, so it isn't called if
+    /// that method is overridden. Once it returns, the screen is re-drawn.
+    ///
+    /// Arguments:
+    /// * `input` - The input event to handle.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_input(&mut self, input: InputEvent, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
+
+    /// Called when the terminal is resized.
+    ///
+    /// It's called with the new size instead of [`Screen::on_event()`], before the screen is
+    /// re-drawn. Use it to recalculate any layout-dependent state, like how many rows fit on the
+    /// screen. By default, it passes the [`Event::Resize`] event on to [`Screen::on_event()`].
+    ///
+    /// Arguments:
+    /// * `cols` - The new number of columns of the terminal.
+    /// * `rows` - The new number of rows of the terminal.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_resize(&mut self, cols: u16, rows: u16, navigator: Navigator<ID, E>) -> HookResult {
+        self.on_event(&Event::Resize(cols, rows), navigator).await
+    }
+
+    /// Called when the terminal gains focus.
+    ///
+    /// It's called instead of [`Screen::on_event()`], which it passes the [`Event::FocusGained`]
+    /// event on to by default. Terminals only report focus changes when they're enabled with
+    /// [`App::with_focus_events()`](crate::App::with_focus_events).
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_focus_gained(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        self.on_event(&Event::FocusGained, navigator).await
+    }
+
+    /// Called when the terminal loses focus.
+    ///
+    /// It's called instead of [`Screen::on_event()`], which it passes the [`Event::FocusLost`]
+    /// event on to by default. Terminals only report focus changes when they're enabled with
+    /// [`App::with_focus_events()`](crate::App::with_focus_events).
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_focus_lost(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        self.on_event(&Event::FocusLost, navigator).await
+    }
+
+    /// Called when text is pasted into the terminal.
+    ///
+    /// It's called with the pasted text instead of [`Screen::on_event()`], which it passes the
+    /// [`Event::Paste`] event on to by default. Text inputs can take the text from here without
+    /// matching on events. Terminals only report pastes when bracketed paste is enabled with
+    /// [`App::with_bracketed_paste()`](crate::App::with_bracketed_paste). The text may be long, so
+    /// avoid blocking on it.
+    ///
+    /// Arguments:
+    /// * `content` - The pasted text.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_paste(&mut self, content: String, navigator: Navigator<ID, E>) -> HookResult {
+        self.on_event(&Event::Paste(content), navigator).await
+    }
+
+    /// Called when no terminal event arrived for as long as the event timeout set with
+    /// [`App::with_event_timeout()`](crate::App::with_event_timeout).
+    ///
+    /// It's called again after every timeout that passes without events, which suits screens that
+    /// refresh on their own while the user is idle, like dashboards. Without an event timeout, it's
+    /// never called.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_event_timeout(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
+
+    /// Called before navigating away from the screen, to confirm the transition.
+    ///
+    /// It's called when a [`Navigator::push()`], [`Navigator::replace()`] or [`Navigator::back()`]
+    /// action is processed while the screen is on top of the stack. Returning `false` drops the
+    /// action, so the screen stays where it is. Use it to keep users from losing unsaved changes,
+    /// for example by showing a confirmation dialog instead. Defaults to `true`.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn can_leave(&mut self, navigator: Navigator<ID, E>) -> bool {
+        true
+    }
+
+    /// Called before navigating to the screen, to confirm the transition.
+    ///
+    /// It's called on the screen a [`Navigator::push()`], [`Navigator::replace()`] or
+    /// [`Navigator::back()`] action would bring to the top of the stack, after the current screen's
+    /// [`Screen::can_leave()`]. Returning `false` drops the action. When pushing or replacing, the
+    /// screen was just created and is discarded. Use it to keep users out of screens they can't
+    /// access yet, like screens requiring authentication. Defaults to `true`.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn can_enter(&mut self, navigator: Navigator<ID, E>) -> bool {
+        true
+    }
+
+    /// The terminal title to set while the screen is on top of the stack.
+    ///
+    /// It's called every time the screen becomes the current one, i.e. when it's entered or
+    /// resumed, and the terminal title is updated if it returns `Some`. Use
+    /// [`Navigator::set_terminal_title()`] to change the title at any other time. Defaults to
+    /// `None`, which leaves the title as it is.
+    fn title(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether the screen is drawn on top of the previous one instead of replacing it.
+    ///
+    /// Overlay screens are drawn after the screens below them, which act as a backdrop, so they're
+    /// useful for modals, tooltips, and popups. Draw only the area you need, clearing it first with
+    /// [`Clear`](ratatui::widgets::Clear) if necessary. Screens pushed with
+    /// [`Navigator::push_overlay()`] are drawn as overlays no matter what this method returns.
+    /// Defaults to `false`.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// Called when a custom event is sent with [`Navigator::send_event()`].
+    ///
+    /// Custom events let background tasks notify the current screen of domain-specific things, like
+    /// a finished download, without going through the application state. Their type is set with
+    /// [`App::with_custom_events()`](crate::App::with_custom_events).
+    ///
+    /// Arguments:
+    /// * `event` - The custom event.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_custom_event(&mut self, event: E, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
+
+    /// Returns how often [`Screen::on_tick()`] is called while the screen is active.
+    ///
+    /// It's checked on every iteration of the event loop, so it can change over time, like to stop
+    /// an animation once it's done. Defaults to `None`, meaning the screen doesn't tick.
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Called periodically while the screen is active, as often as [`Screen::tick_interval()`]
+    /// says.
+    ///
+    /// Useful for animations, like spinners, without spawning a task to request re-draws. Ticks
+    /// that are missed because the screen was busy are skipped rather than delivered in a burst.
+    ///
+    /// Arguments:
+    /// * `tick` - How many times the hook was called on this screen before, starting at 0. Take
+    ///   it modulo the number of frames to animate something.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_tick(&mut self, tick: u64, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
+
+    /// Returns the screen's key bindings, along with a description of what they do.
+    ///
+    /// They're shown in a help overlay when the help key is pressed, `?` by default (see
+    /// [`App::with_help_key()`](crate::App::with_help_key)). The bindings are read when the overlay
+    /// is opened, and the help key is passed to [`Screen::on_event()`] as usual if there are none.
+    /// Defaults to no bindings.
+    fn help_text(&self) -> Vec<(KeyCode, &'static str)> {
+        Vec::new()
+    }
+
+    /// Whether the screen receives the quit keys instead of them exiting the application.
+    ///
+    /// Quit keys are set with [`App::with_quit_key()`](crate::App::with_quit_key). Return `true`
+    /// from screens where they're regular input, like text fields, so they reach
+    /// [`Screen::on_event()`]. Defaults to `false`.
+    fn consumes_quit_key(&self) -> bool {
+        false
+    }
+
+    /// Whether an event is passed to [`Screen::on_event()`].
+    ///
+    /// It's also asked for the events with their own hooks, like [`Screen::on_resize()`] and
+    /// [`Screen::on_paste()`], which are only called for the events it lets through.
+    ///
+    /// Return `false` to skip the events the screen doesn't care about, like mouse or resize
+    /// events. The [`filter`](crate::filter) module has common filters, like
+    /// [`keys_only()`](crate::filter::keys_only). Defaults to passing all events.
+    ///
+    /// Arguments:
+    /// * `event` - The event about to be passed to the screen.
+    fn filter_event(&self, event: &Event) -> bool {
+        true
+    }
+
+    /// Captures which part of the screen has the focus, to restore it on the screen replacing it.
+    ///
+    /// It's called on the current screen when it's replaced with
+    /// [`Navigator::replace_with_focus()`] and [`FocusTransferPolicy::Preserve`], and the returned
+    /// focus is passed to the new screen's [`Screen::restore_focus()`]. Defaults to `None`, which
+    /// leaves the new screen's focus as it is.
+    ///
+    /// [`FocusTransferPolicy::Preserve`]: crate::FocusTransferPolicy::Preserve
+    fn capture_focus(&self) -> Option<FocusState> {
+        None
+    }
+
+    /// Restores the focus captured by [`Screen::capture_focus()`] on the screen it replaced.
+    ///
+    /// It's called right before [`Screen::on_enter()`]. The focus is type-erased, so downcast it to
+    /// the type the other screen captured:
+    ///
+    /// ```ignore
+    /// fn restore_focus(&mut self, focus: FocusState) {
+    ///     if let Ok(field) = focus.downcast::<FormField>() {
+    ///         self.focused = *field;
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Arguments:
+    /// * `focus` - The focus captured on the replaced screen.
+    fn restore_focus(&mut self, focus: FocusState) {}
 
     /// Called when the screen is entered.
     ///
@@ -110,7 +630,9 @@ pub trait Screen<ID>: Default {
     ///
     /// Arguments:
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_enter(&mut self, navigator: Navigator<ID>) {}
+    async fn on_enter(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is exited.
     ///
@@ -120,7 +642,9 @@ pub trait Screen<ID>: Default {
     ///
     /// Arguments:
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_exit(&mut self, navigator: Navigator<ID>) {}
+    async fn on_exit(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is paused (sent to the background because of [`Navigator::push()`]).
     ///
@@ -128,7 +652,9 @@ pub trait Screen<ID>: Default {
     ///
     /// Arguments:
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_pause(&mut self, navigator: Navigator<ID>) {}
+    async fn on_pause(&mut self, navigator: Navigator<ID, E>) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is resumed (brought back to the foreground by [`Navigator::back()`]
     /// or similar).
@@ -136,8 +662,46 @@ pub trait Screen<ID>: Default {
     /// This method can be used to resume any paused tasks or animations.
     ///
     /// Arguments:
+    /// * `reason` - Why the screen was resumed.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    async fn on_resume(
+        &mut self,
+        reason: ResumeReason<ID>,
+        navigator: Navigator<ID, E>,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Called with the data sent by the screen on top of this one with
+    /// [`Navigator::back_with_data()`].
+    ///
+    /// It's called right before [`Screen::on_resume()`]. The data is type-erased, so downcast it
+    /// to the type you expect:
+    ///
+    /// ```ignore
+    /// async fn on_receive(
+    ///     &mut self,
+    ///     data: Box<dyn Any + Send>,
+    ///     navigator: Navigator<ScreenID>,
+    /// ) -> HookResult {
+    ///     if let Ok(color) = data.downcast::<Color>() {
+    ///         self.color = *color;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Arguments:
+    /// * `data` - The data sent by the popped screen.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_resume(&mut self, navigator: Navigator<ID>) {}
+    async fn on_receive(
+        &mut self,
+        data: Box<dyn Any + Send>,
+        navigator: Navigator<ID, E>,
+    ) -> HookResult {
+        Ok(())
+    }
 
     /// An asynchronous task that runs in loop the background.
     ///
@@ -145,37 +709,404 @@ pub trait Screen<ID>: Default {
     /// long-lived tasks prefer spawning a separate tokio task inside [`Screen::on_enter()`] and
     /// managing its lifecycle manually.
     ///
-    /// It will only be run when the screen is active (in the foreground).
+    /// It will only be run when the screen is active (in the foreground). The application loop
+    /// drops the running call whenever an event or navigation action arrives, and starts a new
+    /// one once it has been handled. It doesn't run while the screen is paused, and is stopped
+    /// for good once the screen exits.
+    ///
+    /// Since the task borrows the screen mutably, it never runs at the same time as the other
+    /// hooks. If some I/O (e.g. a network request) must keep going while events are handled, move
+    /// it into a spawned tokio task that owns what it needs (such as a [`State`](crate::State)
+    /// clone), and call [`Navigator::redraw()`] from it once the screen should be updated.
     ///
     /// Arguments:
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn task(&mut self, navigator: Navigator<ID>) {
+    async fn task(&mut self, navigator: Navigator<ID, E>) {
         future::pending().await
     }
 }
 
 /// A screen in the application with access to global application state.
-pub trait ScreenWithState<ID, State> {
+///
+/// Every [`Screen`] implements this trait through a blanket implementation, which is what lets
+/// stateless and stateful screens live in the same `#[derive(Screens)]` enum. As a consequence, a
+/// type implements one of the two traits, never both. To give a stateless screen access to the
+/// state, replace its `Screen` implementation with a `ScreenWithState` one: the methods are the
+/// same, with an extra `state` argument, and the rest of the application doesn't change.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a screen with `{ID}` IDs and `{State}` state",
+    label = "`{Self}` must implement `Screen<{ID}>` or `ScreenWithState<{ID}, {State}>`",
+    note = "every variant of a `#[derive(Screens)]` enum must hold a type implementing `Screen<{ID}>` or `ScreenWithState<{ID}, {State}>`"
+)]
+#[allow(unused_variables)]
+pub trait ScreenWithState<ID, State, E = ()> {
+    /// The screen's name, for status bars and debug overlays.
+    ///
+    /// It's returned by [`Navigator::current_screen_name()`] while the screen is the current one.
+    /// Defaults to an empty string, in which case the name of the screen's `#[derive(Screens)]`
+    /// variant is used instead. The variant can also set it with `#[screen(name = "...")]`.
+    const NAME: &'static str = "";
+
+    /// A short description of the screen, for status bars and debug overlays.
+    ///
+    /// It's returned by [`Navigator::current_screen_description()`] while the screen is the
+    /// current one. Defaults to an empty string. The screen's `#[derive(Screens)]` variant can
+    /// also set it with `#[screen(description = "...")]`.
+    const DESCRIPTION: &'static str = "";
+
+    /// Creates the screen when it's navigated to, or returns `None` if it can't be created.
+    ///
+    /// When it returns `None`, the navigation action is dropped and the current screen stays, and
+    /// the handler set with [`App::with_navigation_error_handler()`] is called. Useful for screens
+    /// that can't be shown without something they load when created, like a file. By default, the
+    /// screen is created with [`Default`].
+    ///
+    /// [`App::with_navigation_error_handler()`]: crate::App::with_navigation_error_handler
+    fn try_new() -> Option<Self>
+    where
+        Self: Default,
+    {
+        Some(Self::default())
+    }
+
     /// Draws the screen.
     ///
+    /// Deprecated in favor of [`ScreenWithState::draw_with_context()`], which calls it by default
+    /// and gets the same frame and state along with how the screen is being drawn. It draws
+    /// nothing by default.
+    ///
+    /// Arguments:
+    /// * `frame` - The frame to draw on.
+    /// * `state` - The state of the application.
+    #[deprecated(note = "implement `ScreenWithState::draw_with_context()` instead")]
+    fn draw(&mut self, frame: &mut Frame, state: &State) {}
+
+    /// Draws the screen, with information about how it's being drawn.
+    ///
     /// Even though this method takes `&mut self`, it's usually not a good idea to modify the
     /// screen state here, as it can lead to unexpected behavior.
     ///
+    /// The context tells the screen about the navigation stack, whether it's drawn as an overlay,
+    /// and the size of the area it's drawn in. Check out [`DrawContext`] for what it holds. It
+    /// calls the deprecated [`ScreenWithState::draw()`] by default, so screens implementing that
+    /// one keep working.
+    ///
     /// Arguments:
     /// * `frame` - The frame to draw on.
+    /// * `context` - How the screen is being drawn.
     /// * `state` - The state of the application.
-    fn draw(&mut self, frame: &mut Frame, state: &State);
+    fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<ID>, state: &State) {
+        #[allow(deprecated)]
+        self.draw(frame, state);
+    }
+
+    /// Returns the area of the terminal the screen wants to be drawn on.
+    ///
+    /// The returned area is the one [`ScreenWithState::draw_with_context()`] receives as
+    /// `frame.area()`, which is useful for screens that only take part of the terminal, like a
+    /// centered dialog. It's clamped to the terminal's area. By default, the screen takes the whole terminal.
+    ///
+    /// Arguments:
+    /// * `full_area` - The area of the whole terminal.
+    /// * `state` - The state of the application.
+    fn preferred_area(&self, full_area: Rect, state: &State) -> Rect {
+        full_area
+    }
 
     /// Handles a terminal event.
     ///
     /// Every time an event is received, this method is called with the event and a navigator. Once
     /// it returns, the screen is re-drawn.
     ///
+    /// The event is passed by reference to avoid cloning large events like pastes. Clone it if you
+    /// need to keep it around.
+    ///
+    /// By default, the event is converted into an [`InputEvent`] and passed to
+    /// [`ScreenWithState::on_input()`], which is easier to implement. Override this method instead
+    /// when you need the full crossterm event.
+    ///
     /// Arguments:
     /// * `event` - The terminal event to handle.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
     /// * `state` - The state of the application.
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ID>, state: &mut State) {}
+    async fn on_event(
+        &mut self,
+        event: &Event,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        self.on_input(event.into(), navigator, state).await
+    }
+
+    /// Handles a terminal event, simplified into an [`InputEvent`].
+    ///
+    /// It's called by the default [`ScreenWithState::on_event()`] implementation, so it isn't
+    /// called if that method is overridden. Once it returns, the screen is re-drawn.
+    ///
+    /// Arguments:
+    /// * `input` - The input event to handle.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_input(
+        &mut self,
+        input: InputEvent,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Called when the terminal is resized.
+    ///
+    /// It's called with the new size instead of [`ScreenWithState::on_event()`], before the
+    /// screen is re-drawn. Use it to recalculate any layout-dependent state, like how many rows
+    /// fit on the screen. By default, it passes the [`Event::Resize`] event on to
+    /// [`ScreenWithState::on_event()`].
+    ///
+    /// Arguments:
+    /// * `cols` - The new number of columns of the terminal.
+    /// * `rows` - The new number of rows of the terminal.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_resize(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        self.on_event(&Event::Resize(cols, rows), navigator, state)
+            .await
+    }
+
+    /// Called when the terminal gains focus.
+    ///
+    /// It's called instead of [`ScreenWithState::on_event()`], which it passes the
+    /// [`Event::FocusGained`] event on to by default. Terminals only report focus changes
+    /// when they're enabled with [`App::with_focus_events()`](crate::App::with_focus_events).
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_focus_gained(
+        &mut self,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        self.on_event(&Event::FocusGained, navigator, state).await
+    }
+
+    /// Called when the terminal loses focus.
+    ///
+    /// It's called instead of [`ScreenWithState::on_event()`], which it passes the
+    /// [`Event::FocusLost`] event on to by default. Terminals only report focus changes
+    /// when they're enabled with [`App::with_focus_events()`](crate::App::with_focus_events).
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_focus_lost(
+        &mut self,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        self.on_event(&Event::FocusLost, navigator, state).await
+    }
+
+    /// Called when text is pasted into the terminal.
+    ///
+    /// It's called with the pasted text instead of [`ScreenWithState::on_event()`], which it
+    /// passes the [`Event::Paste`] event on to by default. Text inputs can take the text from here
+    /// without matching on events. Terminals only report pastes when bracketed paste is enabled
+    /// with [`App::with_bracketed_paste()`](crate::App::with_bracketed_paste). The text may be
+    /// long, so avoid blocking on it.
+    ///
+    /// Arguments:
+    /// * `content` - The pasted text.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_paste(
+        &mut self,
+        content: String,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        self.on_event(&Event::Paste(content), navigator, state)
+            .await
+    }
+
+    /// Called when no terminal event arrived for as long as the event timeout set with
+    /// [`App::with_event_timeout()`](crate::App::with_event_timeout).
+    ///
+    /// It's called again after every timeout that passes without events, which suits screens that
+    /// refresh on their own while the user is idle, like dashboards. Without an event timeout, it's
+    /// never called.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_event_timeout(
+        &mut self,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Called before navigating away from the screen, to confirm the transition.
+    ///
+    /// It's called when a [`Navigator::push()`], [`Navigator::replace()`] or [`Navigator::back()`]
+    /// action is processed while the screen is on top of the stack. Returning `false` drops the
+    /// action, so the screen stays where it is. Defaults to `true`.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn can_leave(&mut self, navigator: Navigator<ID, E>, state: &mut State) -> bool {
+        true
+    }
+
+    /// Called before navigating to the screen, to confirm the transition.
+    ///
+    /// It's called on the screen a [`Navigator::push()`], [`Navigator::replace()`] or
+    /// [`Navigator::back()`] action would bring to the top of the stack, after the current screen's
+    /// [`ScreenWithState::can_leave()`]. Returning `false` drops the action. When pushing or
+    /// replacing, the screen was just created and is discarded. Defaults to `true`.
+    ///
+    /// Arguments:
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn can_enter(&mut self, navigator: Navigator<ID, E>, state: &mut State) -> bool {
+        true
+    }
+
+    /// The terminal title to set while the screen is on top of the stack.
+    ///
+    /// It's called every time the screen becomes the current one, i.e. when it's entered or
+    /// resumed, and the terminal title is updated if it returns `Some`. Defaults to `None`, which
+    /// leaves the title as it is.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    fn title(&self, state: &State) -> Option<String> {
+        None
+    }
+
+    /// Whether the screen is drawn on top of the previous one instead of replacing it.
+    ///
+    /// Overlay screens are drawn after the screens below them, which act as a backdrop. Screens
+    /// pushed with [`Navigator::push_overlay()`] are drawn as overlays no matter what this method
+    /// returns. Defaults to `false`.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    fn is_overlay(&self, state: &State) -> bool {
+        false
+    }
+
+    /// Called when a custom event is sent with [`Navigator::send_event()`].
+    ///
+    /// Arguments:
+    /// * `event` - The custom event.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_custom_event(
+        &mut self,
+        event: E,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Returns how often [`ScreenWithState::on_tick()`] is called while the screen is active.
+    ///
+    /// It's checked on every iteration of the event loop, so it can change over time. Defaults to
+    /// `None`, meaning the screen doesn't tick.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    fn tick_interval(&self, state: &State) -> Option<Duration> {
+        None
+    }
+
+    /// Called periodically while the screen is active, as often as
+    /// [`ScreenWithState::tick_interval()`] says.
+    ///
+    /// Arguments:
+    /// * `tick` - How many times the hook was called on this screen before, starting at 0.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
+    /// * `state` - The state of the application.
+    async fn on_tick(
+        &mut self,
+        tick: u64,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Returns the screen's key bindings, along with a description of what they do.
+    ///
+    /// They're shown in a help overlay when the help key is pressed. Defaults to no bindings.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    fn help_text(&self, state: &State) -> Vec<(KeyCode, &'static str)> {
+        Vec::new()
+    }
+
+    /// Whether the screen receives the quit keys instead of them exiting the application.
+    ///
+    /// Quit keys are set with [`App::with_quit_key()`](crate::App::with_quit_key). Defaults to
+    /// `false`.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    fn consumes_quit_key(&self, state: &State) -> bool {
+        false
+    }
+
+    /// Whether an event is passed to [`ScreenWithState::on_event()`].
+    ///
+    /// It's also asked for the events with their own hooks, like
+    /// [`ScreenWithState::on_resize()`] and [`ScreenWithState::on_paste()`], which are only called
+    /// for the events it lets through.
+    ///
+    /// Defaults to passing all events.
+    ///
+    /// Arguments:
+    /// * `event` - The event about to be passed to the screen.
+    /// * `state` - The state of the application.
+    fn filter_event(&self, event: &Event, state: &State) -> bool {
+        true
+    }
+
+    /// Captures which part of the screen has the focus, to restore it on the screen replacing it.
+    ///
+    /// It's called on the current screen when it's replaced with
+    /// [`Navigator::replace_with_focus()`] and [`FocusTransferPolicy::Preserve`], and the returned
+    /// focus is passed to the new screen's [`ScreenWithState::restore_focus()`]. Defaults to
+    /// `None`, which leaves the new screen's focus as it is.
+    ///
+    /// Arguments:
+    /// * `state` - The state of the application.
+    ///
+    /// [`FocusTransferPolicy::Preserve`]: crate::FocusTransferPolicy::Preserve
+    fn capture_focus(&self, state: &State) -> Option<FocusState> {
+        None
+    }
+
+    /// Restores the focus captured by [`ScreenWithState::capture_focus()`] on the screen it
+    /// replaced.
+    ///
+    /// It's called right before [`ScreenWithState::on_enter()`]. The focus is type-erased, so
+    /// downcast it to the type the other screen captured.
+    ///
+    /// Arguments:
+    /// * `focus` - The focus captured on the replaced screen.
+    /// * `state` - The state of the application.
+    fn restore_focus(&mut self, focus: FocusState, state: &mut State) {}
 
     /// Called when the screen is entered.
     ///
@@ -186,7 +1117,9 @@ pub trait ScreenWithState<ID, State> {
     /// Arguments:
     /// * `state` - The state of the application.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_enter(&mut self, navigator: Navigator<ID>, state: &mut State) {}
+    async fn on_enter(&mut self, navigator: Navigator<ID, E>, state: &mut State) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is exited.
     ///
@@ -197,7 +1130,9 @@ pub trait ScreenWithState<ID, State> {
     /// Arguments:
     /// * `state` - The state of the application.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_exit(&mut self, navigator: Navigator<ID>, state: &mut State) {}
+    async fn on_exit(&mut self, navigator: Navigator<ID, E>, state: &mut State) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is paused (sent to the background because of [`Navigator::push()`]).
     ///
@@ -206,7 +1141,9 @@ pub trait ScreenWithState<ID, State> {
     /// Arguments:
     /// * `state` - The state of the application.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_pause(&mut self, navigator: Navigator<ID>, state: &mut State) {}
+    async fn on_pause(&mut self, navigator: Navigator<ID, E>, state: &mut State) -> HookResult {
+        Ok(())
+    }
 
     /// Called when the screen is resumed (brought back to the foreground by [`Navigator::back()`]
     /// or similar).
@@ -214,9 +1151,36 @@ pub trait ScreenWithState<ID, State> {
     /// This method can be used to resume any paused tasks or animations.
     ///
     /// Arguments:
+    /// * `reason` - Why the screen was resumed.
+    /// * `navigator` - The navigator to navigate between screens or request re-draws.
     /// * `state` - The state of the application.
+    async fn on_resume(
+        &mut self,
+        reason: ResumeReason<ID>,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
+
+    /// Called with the data sent by the screen on top of this one with
+    /// [`Navigator::back_with_data()`].
+    ///
+    /// It's called right before [`ScreenWithState::on_resume()`]. The data is type-erased, so
+    /// downcast it to the type you expect.
+    ///
+    /// Arguments:
+    /// * `data` - The data sent by the popped screen.
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
-    async fn on_resume(&mut self, navigator: Navigator<ID>, state: &mut State) {}
+    /// * `state` - The state of the application.
+    async fn on_receive(
+        &mut self,
+        data: Box<dyn Any + Send>,
+        navigator: Navigator<ID, E>,
+        state: &mut State,
+    ) -> HookResult {
+        Ok(())
+    }
 
     /// An asynchronous task that runs in loop the background.
     ///
@@ -224,46 +1188,191 @@ pub trait ScreenWithState<ID, State> {
     /// long-lived tasks prefer spawning a separate tokio task inside [`Screen::on_enter()`] and
     /// managing its lifecycle manually.
     ///
-    /// It will only be run when the screen is active (in the foreground).
+    /// It will only be run when the screen is active (in the foreground). The application loop
+    /// drops the running call whenever an event or navigation action arrives, and starts a new
+    /// one once it has been handled. It doesn't run while the screen is paused, and is stopped
+    /// for good once the screen exits.
+    ///
+    /// Since the task borrows the screen mutably, it never runs at the same time as the other
+    /// hooks. If some I/O (e.g. a network request) must keep going while events are handled, move
+    /// it into a spawned tokio task that owns what it needs (such as a [`State`](crate::State)
+    /// clone), and call [`Navigator::redraw()`] from it once the screen should be updated.
+    ///
+    /// The same goes for the application state: the task is dropped before any other hook gets
+    /// `state`, so holding `&mut State` across `.await` points is fine. Any change that must not
+    /// be lost halfway through should be applied without awaiting in between, though, since the
+    /// task can be cancelled at any `.await`.
     ///
     /// Arguments:
     /// * `navigator` - The navigator to navigate between screens or request re-draws.
     /// * `state` - The state of the application.
-    async fn task(&mut self, navigator: Navigator<ID>, state: &mut State) {
+    async fn task(&mut self, navigator: Navigator<ID, E>, state: &mut State) {
         future::pending().await
     }
 }
 
 // All [`Screen`]s are a [`ScreenWithState`] under the hood.
-impl<ID, T, S> ScreenWithState<ID, T> for S
+impl<ID, T, E, S> ScreenWithState<ID, T, E> for S
 where
-    S: Screen<ID>,
+    S: Screen<ID, E>,
 {
-    fn draw(&mut self, frame: &mut Frame, _state: &T) {
-        self.draw(frame);
+    const NAME: &'static str = <Self as Screen<ID, E>>::NAME;
+    const DESCRIPTION: &'static str = <Self as Screen<ID, E>>::DESCRIPTION;
+
+    fn try_new() -> Option<Self> {
+        <Self as Screen<ID, E>>::try_new()
+    }
+
+    fn draw_with_context(&mut self, frame: &mut Frame, context: DrawContext<ID>, _state: &T) {
+        self.draw_with_context(frame, context);
+    }
+
+    fn preferred_area(&self, full_area: Rect, _state: &T) -> Rect {
+        self.preferred_area(full_area)
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &Event,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_event(event, navigator).await
+    }
+
+    async fn on_input(
+        &mut self,
+        input: InputEvent,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_input(input, navigator).await
+    }
+
+    async fn on_resize(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_resize(cols, rows, navigator).await
+    }
+
+    async fn on_focus_gained(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> HookResult {
+        self.on_focus_gained(navigator).await
+    }
+
+    async fn on_focus_lost(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> HookResult {
+        self.on_focus_lost(navigator).await
+    }
+
+    async fn on_paste(
+        &mut self,
+        content: String,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_paste(content, navigator).await
+    }
+
+    async fn on_event_timeout(
+        &mut self,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_event_timeout(navigator).await
+    }
+
+    async fn can_leave(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> bool {
+        self.can_leave(navigator).await
+    }
+
+    async fn can_enter(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> bool {
+        self.can_enter(navigator).await
+    }
+
+    fn title(&self, _state: &T) -> Option<String> {
+        self.title()
+    }
+
+    fn is_overlay(&self, _state: &T) -> bool {
+        self.is_overlay()
+    }
+
+    async fn on_custom_event(
+        &mut self,
+        event: E,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_custom_event(event, navigator).await
+    }
+
+    fn tick_interval(&self, _state: &T) -> Option<Duration> {
+        self.tick_interval()
+    }
+
+    async fn on_tick(
+        &mut self,
+        tick: u64,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_tick(tick, navigator).await
+    }
+
+    fn help_text(&self, _state: &T) -> Vec<(KeyCode, &'static str)> {
+        self.help_text()
+    }
+
+    fn consumes_quit_key(&self, _state: &T) -> bool {
+        self.consumes_quit_key()
+    }
+
+    fn filter_event(&self, event: &Event, _state: &T) -> bool {
+        self.filter_event(event)
+    }
+
+    fn capture_focus(&self, _state: &T) -> Option<FocusState> {
+        self.capture_focus()
+    }
+
+    fn restore_focus(&mut self, focus: FocusState, _state: &mut T) {
+        self.restore_focus(focus);
     }
 
-    async fn on_event(&mut self, event: Event, navigator: Navigator<ID>, _state: &mut T) {
-        self.on_event(event, navigator).await;
+    async fn on_enter(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> HookResult {
+        self.on_enter(navigator).await
     }
 
-    async fn on_enter(&mut self, navigator: Navigator<ID>, _state: &mut T) {
-        self.on_enter(navigator).await;
+    async fn on_exit(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> HookResult {
+        self.on_exit(navigator).await
     }
 
-    async fn on_exit(&mut self, navigator: Navigator<ID>, _state: &mut T) {
-        self.on_exit(navigator).await;
+    async fn on_pause(&mut self, navigator: Navigator<ID, E>, _state: &mut T) -> HookResult {
+        self.on_pause(navigator).await
     }
 
-    async fn on_pause(&mut self, navigator: Navigator<ID>, _state: &mut T) {
-        self.on_pause(navigator).await;
+    async fn on_resume(
+        &mut self,
+        reason: ResumeReason<ID>,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_resume(reason, navigator).await
     }
 
-    async fn on_resume(&mut self, navigator: Navigator<ID>, _state: &mut T) {
-        self.on_resume(navigator).await;
+    async fn on_receive(
+        &mut self,
+        data: Box<dyn Any + Send>,
+        navigator: Navigator<ID, E>,
+        _state: &mut T,
+    ) -> HookResult {
+        self.on_receive(data, navigator).await
     }
 
-    async fn task(&mut self, navigator: Navigator<ID>, _state: &mut T) {
+    async fn task(&mut self, navigator: Navigator<ID, E>, _state: &mut T) {
         self.task(navigator).await;
     }
 }
@@ -1,9 +1,12 @@
 use std::{
+    fmt, io,
     ops::{Deref, DerefMut},
     sync::Arc,
 };
 
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, watch};
 
 #[derive(Debug, Clone)]
 pub struct State<T>(Arc<Mutex<T>>);
@@ -13,13 +16,71 @@ impl<T> State<T> {
         State(Arc::new(Mutex::new(state)))
     }
 
+    /// Locks the state, blocking the current thread until the lock is available.
+    ///
+    /// Panics if a thread panicked while holding the lock. Use [`State::try_get()`] where
+    /// blocking isn't acceptable.
     pub fn get(&self) -> StateHandle<'_, T> {
         StateHandle(self.0.lock().expect("Failed to lock the application state mutex"))
     }
+
+    /// Locks the state if it's available right away, returning `None` if it's held elsewhere.
+    ///
+    /// Useful in `draw_with_context()` methods, which must not block, to draw a placeholder or the last known
+    /// value while a background task holds the lock. Panics if a thread panicked while holding
+    /// the lock, like [`State::get()`].
+    pub fn try_get(&self) -> Option<StateHandle<'_, T>> {
+        match self.0.try_lock() {
+            Ok(guard) => Some(StateHandle(guard)),
+            Err(TryLockError::WouldBlock) => None,
+            Err(TryLockError::Poisoned(_)) => panic!("Failed to lock the application state mutex"),
+        }
+    }
+
+    /// Returns a clone of the inner value if the state is available right away, or the default
+    /// value if it's held elsewhere.
+    ///
+    /// The default value isn't stored in the state, it's only returned. Check out
+    /// [`State::try_get()`] for more information.
+    pub fn get_or_default(&self) -> T
+    where
+        T: Clone + Default,
+    {
+        self.try_get()
+            .map(|state| state.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns a clone of the inner value, holding the lock only while cloning.
+    pub fn clone_inner(&self) -> T
+    where
+        T: Clone,
+    {
+        self.get().clone()
+    }
+
+    /// Alias of [`State::clone_inner()`].
+    pub fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.clone_inner()
+    }
 }
 
 pub struct StateHandle<'a, T>(MutexGuard<'a, T>);
 
+impl<'a, T> StateHandle<'a, T> {
+    /// Consumes the handle, returning the guard of the state's mutex.
+    ///
+    /// Useful to pass the guard to APIs that take a [`MutexGuard`], like
+    /// [`Condvar::wait()`](std::sync::Condvar::wait). The state stays locked until the guard is
+    /// dropped.
+    pub fn into_inner(self) -> MutexGuard<'a, T> {
+        self.0
+    }
+}
+
 impl<'a, T> Deref for StateHandle<'a, T> {
     type Target = T;
 
@@ -34,6 +95,50 @@ impl<'a, T> DerefMut for StateHandle<'a, T> {
     }
 }
 
+impl<'a, T> AsRef<T> for StateHandle<'a, T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'a, T> AsMut<T> for StateHandle<'a, T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<'a, T> fmt::Debug for StateHandle<'a, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl<'a, T> fmt::Display for StateHandle<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+// Lets a state holding a writer, like a log buffer, be written to with `write!()`.
+impl<'a, T> io::Write for StateHandle<'a, T>
+where
+    T: io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
 impl<T> Default for State<T>
 where
     T: Default,
@@ -42,3 +147,181 @@ where
         State::new(T::default())
     }
 }
+
+/// An application state that can be held across `await` points.
+///
+/// It's a wrapper to [`Arc<RwLock<T>>`](tokio::sync::RwLock) using tokio's asynchronous lock.
+/// Waiting for the lock yields to the runtime instead of blocking the thread, and any number of
+/// readers can hold it at the same time. Use it as the application state and implement
+/// [`ScreenWithState<ID, AsyncState<T>>`](crate::ScreenWithState) for your screens.
+///
+/// Prefer [`State`] when the state is only accessed briefly, since locking it is synchronous and
+/// cheaper. Use `AsyncState` when a handle must be kept across `await` points, or when many
+/// tasks mostly read the state.
+#[derive(Debug)]
+pub struct AsyncState<T>(Arc<RwLock<T>>);
+
+impl<T> AsyncState<T> {
+    pub fn new(state: T) -> Self {
+        AsyncState(Arc::new(RwLock::new(state)))
+    }
+
+    /// Waits until the state can be read, allowing other readers at the same time.
+    pub async fn read(&self) -> AsyncStateReadHandle<'_, T> {
+        AsyncStateReadHandle(self.0.read().await)
+    }
+
+    /// Waits until the state can be written, with no other readers or writers.
+    pub async fn write(&self) -> AsyncStateWriteHandle<'_, T> {
+        AsyncStateWriteHandle(self.0.write().await)
+    }
+}
+
+impl<T> Clone for AsyncState<T> {
+    fn clone(&self) -> Self {
+        AsyncState(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Default for AsyncState<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        AsyncState::new(T::default())
+    }
+}
+
+pub struct AsyncStateReadHandle<'a, T>(RwLockReadGuard<'a, T>);
+
+impl<'a, T> Deref for AsyncStateReadHandle<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct AsyncStateWriteHandle<'a, T>(RwLockWriteGuard<'a, T>);
+
+impl<'a, T> Deref for AsyncStateWriteHandle<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T> DerefMut for AsyncStateWriteHandle<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// An application state that notifies watchers whenever it's modified.
+///
+/// It works like [`State`], but every time a handle returned by [`WatchableState::get_mut()`] is
+/// dropped, the receivers returned by [`WatchableState::watch()`] are notified. This lets
+/// background tasks wait for the state to change instead of polling it on a timer:
+///
+/// ```ignore
+/// let mut changes = state.watch();
+///
+/// while changes.changed().await.is_ok() {
+///     navigator.redraw();
+/// }
+/// ```
+///
+/// Receivers are only notified of the changes, so read the state with
+/// [`WatchableState::get()`] once they wake up.
+#[derive(Debug)]
+pub struct WatchableState<T> {
+    state: State<T>,
+    changes: Arc<watch::Sender<()>>,
+}
+
+impl<T> WatchableState<T> {
+    pub fn new(state: T) -> Self {
+        WatchableState {
+            state: State::new(state),
+            changes: Arc::new(watch::Sender::new(())),
+        }
+    }
+
+    /// Locks the state for reading, without notifying watchers.
+    ///
+    /// The returned handle can't modify the state, since watchers wouldn't be notified. Use
+    /// [`WatchableState::get_mut()`] to modify it.
+    pub fn get(&self) -> WatchableStateReadHandle<'_, T> {
+        WatchableStateReadHandle(self.state.get())
+    }
+
+    /// Locks the state for writing. Watchers are notified once the returned handle is dropped.
+    pub fn get_mut(&self) -> WatchableStateHandle<'_, T> {
+        WatchableStateHandle {
+            handle: self.state.get(),
+            changes: &self.changes,
+        }
+    }
+
+    /// Returns a receiver that's notified every time the state is modified.
+    ///
+    /// Only modifications made after this call are reported by
+    /// [`Receiver::changed()`](watch::Receiver::changed).
+    pub fn watch(&self) -> watch::Receiver<()> {
+        self.changes.subscribe()
+    }
+}
+
+impl<T> Clone for WatchableState<T> {
+    fn clone(&self) -> Self {
+        WatchableState {
+            state: State(Arc::clone(&self.state.0)),
+            changes: Arc::clone(&self.changes),
+        }
+    }
+}
+
+impl<T> Default for WatchableState<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        WatchableState::new(T::default())
+    }
+}
+
+pub struct WatchableStateReadHandle<'a, T>(StateHandle<'a, T>);
+
+impl<'a, T> Deref for WatchableStateReadHandle<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct WatchableStateHandle<'a, T> {
+    handle: StateHandle<'a, T>,
+    changes: &'a watch::Sender<()>,
+}
+
+impl<'a, T> Deref for WatchableStateHandle<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handle
+    }
+}
+
+impl<'a, T> DerefMut for WatchableStateHandle<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handle
+    }
+}
+
+impl<'a, T> Drop for WatchableStateHandle<'a, T> {
+    fn drop(&mut self) {
+        self.changes.send_replace(());
+    }
+}
@@ -0,0 +1,136 @@
+//! Checks that every screen in the stack exits when drawing to the terminal fails.
+
+use std::{future, io};
+
+use ratapp::{App, DrawContext, EventSource, HookResult, Navigator, ScreenWithState, Screens};
+use ratatui::{
+    Frame, Terminal,
+    backend::{Backend, TestBackend, WindowSize},
+    buffer::Cell,
+    crossterm::event::Event,
+    layout::{Position, Size},
+};
+
+/// The screens that exited, in order.
+type Exited = Vec<ScreenID>;
+
+#[derive(Screens)]
+enum TestScreens {
+    #[screen(default)]
+    Root(RootScreen),
+    Top(TopScreen),
+}
+
+#[derive(Default)]
+struct RootScreen;
+
+impl ScreenWithState<ScreenID, Exited> for RootScreen {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &Exited,
+    ) {
+        frame.render_widget("Root", frame.area());
+    }
+
+    async fn on_enter(
+        &mut self,
+        navigator: Navigator<ScreenID>,
+        _state: &mut Exited,
+    ) -> HookResult {
+        navigator.push(ScreenID::Top);
+
+        Ok(())
+    }
+
+    async fn on_exit(&mut self, _navigator: Navigator<ScreenID>, state: &mut Exited) -> HookResult {
+        state.push(ScreenID::Root);
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct TopScreen;
+
+impl ScreenWithState<ScreenID, Exited> for TopScreen {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &Exited,
+    ) {
+        frame.render_widget("Top", frame.area());
+    }
+
+    async fn on_exit(&mut self, _navigator: Navigator<ScreenID>, state: &mut Exited) -> HookResult {
+        state.push(ScreenID::Top);
+
+        Ok(())
+    }
+}
+
+/// A [`TestBackend`] that fails to flush what's drawn, like a terminal that was closed.
+struct FailingBackend(TestBackend);
+
+impl Backend for FailingBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.0.draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.0.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.0.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.0.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.0.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.0.clear()
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.0.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.0.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Err(io::Error::other("the terminal was closed"))
+    }
+}
+
+/// An [`EventSource`] that never produces events, so the application doesn't read the terminal.
+struct NoEvents;
+
+impl EventSource for NoEvents {
+    async fn next_event(&mut self) -> Option<Event> {
+        future::pending().await
+    }
+}
+
+#[tokio::test]
+async fn screens_exit_when_drawing_fails() {
+    let mut terminal = Terminal::new(FailingBackend(TestBackend::new(80, 24))).unwrap();
+    let mut app = App::with_state(Exited::new()).with_event_source(NoEvents);
+
+    let result = app.run_with_terminal::<TestScreens, _>(&mut terminal).await;
+
+    assert_eq!(result.unwrap_err().to_string(), "the terminal was closed");
+    assert_eq!(app.into_state(), [ScreenID::Top, ScreenID::Root]);
+}
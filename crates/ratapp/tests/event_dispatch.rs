@@ -0,0 +1,383 @@
+//! Checks which hooks terminal and custom events are dispatched to, running the application
+//! headlessly.
+
+use ratapp::{App, DrawContext, EventPriority, HookResult, Navigator, ScreenWithState, Screens};
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+};
+
+#[derive(Default)]
+struct TestState {
+    /// The events the screens' `filter_event` hook rejects.
+    filtered: Vec<Event>,
+    /// Whether the screens have key bindings to show in the help overlay.
+    help: bool,
+    /// Whether the screens handle the quit keys themselves.
+    consume_quit_key: bool,
+    /// What the screens got, in order.
+    log: Vec<String>,
+}
+
+/// Declares screens logging the events they get.
+///
+/// Pressing `n` pushes two screens, and pressing `e` sends a custom event.
+macro_rules! logging_screens {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Default)]
+            struct $name;
+
+            impl ScreenWithState<ScreenID, TestState, String> for $name {
+                fn draw_with_context(
+                    &mut self,
+                    _frame: &mut Frame,
+                    _context: DrawContext<ScreenID>,
+                    _state: &TestState,
+                ) {
+                }
+
+                async fn on_event(
+                    &mut self,
+                    event: &Event,
+                    navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    let name = stringify!($name);
+                    let Event::Key(key) = event else {
+                        state.log.push(format!("{name} got {event:?}"));
+                        return Ok(());
+                    };
+
+                    state.log.push(format!("{name} got key {}", key.code));
+
+                    match key.code {
+                        KeyCode::Char('n') => {
+                            navigator.push(ScreenID::First);
+                            navigator.push(ScreenID::Second);
+                        }
+                        KeyCode::Char('e') => navigator.send_event(format!("sent by {name}")),
+                        _ => {}
+                    }
+
+                    Ok(())
+                }
+
+                async fn on_resize(
+                    &mut self,
+                    cols: u16,
+                    rows: u16,
+                    _navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state
+                        .log
+                        .push(format!("{} resized to {cols}x{rows}", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_focus_gained(
+                    &mut self,
+                    _navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state.log.push(format!("{} gained focus", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_focus_lost(
+                    &mut self,
+                    _navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state.log.push(format!("{} lost focus", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_paste(
+                    &mut self,
+                    content: String,
+                    _navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state
+                        .log
+                        .push(format!("{} got paste {content}", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_custom_event(
+                    &mut self,
+                    event: String,
+                    _navigator: Navigator<ScreenID, String>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state
+                        .log
+                        .push(format!("{} got custom event {event}", stringify!($name)));
+
+                    Ok(())
+                }
+
+                fn filter_event(&self, event: &Event, state: &TestState) -> bool {
+                    !state.filtered.contains(event)
+                }
+
+                fn help_text(&self, state: &TestState) -> Vec<(KeyCode, &'static str)> {
+                    if state.help {
+                        vec![(KeyCode::Char('n'), "Push two screens")]
+                    } else {
+                        Vec::new()
+                    }
+                }
+
+                fn consumes_quit_key(&self, state: &TestState) -> bool {
+                    state.consume_quit_key
+                }
+            }
+        )*
+    };
+}
+
+logging_screens!(Home, First, Second);
+
+#[derive(Screens)]
+enum TestScreens {
+    #[screen(default)]
+    Home(Home),
+    First(First),
+    Second(Second),
+}
+
+/// A screen only implementing `on_event`, leaving the dedicated hooks to their defaults.
+#[derive(Default)]
+struct PlainScreen;
+
+impl ScreenWithState<PlainScreenID, TestState> for PlainScreen {
+    fn draw_with_context(
+        &mut self,
+        _frame: &mut Frame,
+        _context: DrawContext<PlainScreenID>,
+        _state: &TestState,
+    ) {
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &Event,
+        _navigator: Navigator<PlainScreenID>,
+        state: &mut TestState,
+    ) -> HookResult {
+        state.log.push(format!("Plain got {event:?}"));
+
+        Ok(())
+    }
+}
+
+#[derive(Screens)]
+#[screen(id_name = "PlainScreenID")]
+enum PlainScreens {
+    #[screen(default)]
+    Plain(PlainScreen),
+}
+
+/// Presses the key with the given code.
+fn key(code: KeyCode) -> Event {
+    Event::Key(code.into())
+}
+
+/// Runs the application with `state`, configured by `configure`, handling `events`, and returns
+/// what the screens got.
+async fn run(
+    state: TestState,
+    events: Vec<Event>,
+    configure: impl FnOnce(App<TestState, String>) -> App<TestState, String>,
+) -> Vec<String> {
+    let app = App::with_state(state).with_custom_events::<String>();
+
+    configure(app)
+        .run_headless::<TestScreens>(events)
+        .await
+        .unwrap()
+        .log
+}
+
+#[tokio::test]
+async fn resizes_focus_changes_and_pastes_go_to_their_hooks() {
+    let events = vec![
+        Event::Resize(100, 30),
+        Event::FocusGained,
+        Event::FocusLost,
+        Event::Paste("text".into()),
+        key(KeyCode::Char('a')),
+    ];
+
+    let log = run(TestState::default(), events, |app| app).await;
+
+    assert_eq!(
+        log,
+        [
+            "Home resized to 100x30",
+            "Home gained focus",
+            "Home lost focus",
+            "Home got paste text",
+            "Home got key a",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn default_dedicated_hooks_pass_events_to_on_event() {
+    let events = vec![
+        Event::Resize(100, 30),
+        Event::FocusGained,
+        Event::FocusLost,
+        Event::Paste("text".into()),
+    ];
+
+    let log = App::with_state(TestState::default())
+        .run_headless::<PlainScreens>(events)
+        .await
+        .unwrap()
+        .log;
+
+    assert_eq!(
+        log,
+        [
+            "Plain got Resize(100, 30)",
+            "Plain got FocusGained",
+            "Plain got FocusLost",
+            "Plain got Paste(\"text\")",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn filter_event_rejects_events_before_any_hook() {
+    let state = TestState {
+        filtered: vec![key(KeyCode::Char('x')), Event::FocusGained],
+        ..TestState::default()
+    };
+    let events = vec![
+        key(KeyCode::Char('x')),
+        Event::FocusGained,
+        Event::Paste("text".into()),
+        key(KeyCode::Char('y')),
+    ];
+
+    let log = run(state, events, |app| app).await;
+
+    assert_eq!(log, ["Home got paste text", "Home got key y"]);
+}
+
+#[tokio::test]
+async fn custom_events_go_to_on_custom_event() {
+    let log = run(TestState::default(), vec![key(KeyCode::Char('e'))], |app| {
+        app
+    })
+    .await;
+
+    assert_eq!(
+        log,
+        ["Home got key e", "Home got custom event sent by Home"]
+    );
+}
+
+#[tokio::test]
+async fn quit_keys_exit_before_reaching_the_screen() {
+    let events = vec![
+        key(KeyCode::Char('a')),
+        Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+        key(KeyCode::Char('q')),
+        key(KeyCode::Char('b')),
+    ];
+
+    let log = run(TestState::default(), events, |app| {
+        app.with_quit_key(KeyCode::Char('q'))
+    })
+    .await;
+
+    // Only unmodified presses quit, so Ctrl+Q still reaches the screen.
+    assert_eq!(log, ["Home got key a", "Home got key q"]);
+}
+
+#[tokio::test]
+async fn quit_keys_reach_screens_consuming_them() {
+    let state = TestState {
+        consume_quit_key: true,
+        ..TestState::default()
+    };
+    let events = vec![key(KeyCode::Char('q')), key(KeyCode::Char('b'))];
+
+    let log = run(state, events, |app| app.with_quit_key(KeyCode::Char('q'))).await;
+
+    assert_eq!(log, ["Home got key q", "Home got key b"]);
+}
+
+#[tokio::test]
+async fn help_overlay_takes_key_presses_while_open() {
+    let state = TestState {
+        help: true,
+        ..TestState::default()
+    };
+    let events = vec![
+        key(KeyCode::Char('?')),
+        key(KeyCode::Char('a')),
+        key(KeyCode::Esc),
+        key(KeyCode::Char('b')),
+    ];
+
+    let log = run(state, events, |app| app).await;
+
+    assert_eq!(log, ["Home got key b"]);
+}
+
+#[tokio::test]
+async fn help_key_reaches_screens_without_key_bindings() {
+    let log = run(TestState::default(), vec![key(KeyCode::Char('?'))], |app| {
+        app
+    })
+    .await;
+
+    assert_eq!(log, ["Home got key ?"]);
+}
+
+#[tokio::test]
+async fn events_priority_handles_events_before_actions() {
+    let events = vec![key(KeyCode::Char('n')), key(KeyCode::Char('x'))];
+
+    let log = run(TestState::default(), events, |app| {
+        app.with_event_priority(EventPriority::Events)
+    })
+    .await;
+
+    assert_eq!(log, ["Home got key n", "Home got key x"]);
+}
+
+#[tokio::test]
+async fn actions_priority_handles_actions_before_events() {
+    let events = vec![key(KeyCode::Char('n')), key(KeyCode::Char('x'))];
+
+    let log = run(TestState::default(), events, |app| {
+        app.with_event_priority(EventPriority::Actions)
+    })
+    .await;
+
+    assert_eq!(log, ["Home got key n", "Second got key x"]);
+}
+
+#[tokio::test]
+async fn alternate_priority_takes_turns() {
+    let events = vec![key(KeyCode::Char('n')), key(KeyCode::Char('x'))];
+
+    let log = run(TestState::default(), events, |app| {
+        app.with_event_priority(EventPriority::Alternate)
+    })
+    .await;
+
+    assert_eq!(log, ["Home got key n", "First got key x"]);
+}
@@ -0,0 +1,232 @@
+//! Checks that the handlers set after `App::with_screens()` take the screens' types without
+//! annotations, and are called while the application runs.
+
+use std::sync::{Arc, Mutex};
+
+use ratapp::{
+    Action, App, DrawContext, HookErrorAction, HookResult, Navigator, ScreenWithState, Screens,
+};
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+};
+
+#[derive(Default)]
+struct TestState {
+    /// The screen the home screen pushes when entered, if any.
+    target: Option<ScreenID>,
+    /// What the screens and handlers saw, in order.
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestState {
+    fn new(target: Option<ScreenID>) -> Self {
+        TestState {
+            target,
+            log: Arc::default(),
+        }
+    }
+}
+
+#[derive(Screens)]
+enum TestScreens {
+    #[screen(default)]
+    Home(HomeScreen),
+    Report(ReportScreen),
+    Denied(DeniedScreen),
+    Failing(FailingScreen),
+}
+
+#[derive(Default)]
+struct HomeScreen;
+
+impl ScreenWithState<ScreenID, TestState> for HomeScreen {
+    fn draw_with_context(
+        &mut self,
+        _frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &TestState,
+    ) {
+    }
+
+    async fn on_enter(
+        &mut self,
+        navigator: Navigator<ScreenID>,
+        state: &mut TestState,
+    ) -> HookResult {
+        if let Some(target) = state.target {
+            navigator.push(target);
+        }
+
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        _event: &Event,
+        _navigator: Navigator<ScreenID>,
+        state: &mut TestState,
+    ) -> HookResult {
+        state.log.lock().unwrap().push("Home got an event".into());
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ReportScreen {
+    from_factory: bool,
+}
+
+impl ScreenWithState<ScreenID, TestState> for ReportScreen {
+    fn draw_with_context(
+        &mut self,
+        _frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &TestState,
+    ) {
+    }
+
+    async fn on_enter(
+        &mut self,
+        navigator: Navigator<ScreenID>,
+        state: &mut TestState,
+    ) -> HookResult {
+        let entry = format!("Report entered, from factory: {}", self.from_factory);
+        state.log.lock().unwrap().push(entry);
+        navigator.exit();
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct DeniedScreen;
+
+impl ScreenWithState<ScreenID, TestState> for DeniedScreen {
+    fn draw_with_context(
+        &mut self,
+        _frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &TestState,
+    ) {
+    }
+
+    async fn on_enter(
+        &mut self,
+        navigator: Navigator<ScreenID>,
+        state: &mut TestState,
+    ) -> HookResult {
+        state.log.lock().unwrap().push("Denied entered".into());
+        navigator.exit();
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct FailingScreen;
+
+impl ScreenWithState<ScreenID, TestState> for FailingScreen {
+    fn draw_with_context(
+        &mut self,
+        _frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &TestState,
+    ) {
+    }
+
+    async fn on_enter(
+        &mut self,
+        _navigator: Navigator<ScreenID>,
+        _state: &mut TestState,
+    ) -> HookResult {
+        Err("Failing screen entered".into())
+    }
+}
+
+#[tokio::test]
+async fn screen_factory_creates_screens() {
+    let state = App::with_state(TestState::new(Some(ScreenID::Report)))
+        .with_screens::<TestScreens>()
+        .with_screen_factory(ScreenID::Report, || async {
+            TestScreens::Report(ReportScreen { from_factory: true })
+        })
+        .run_headless(Vec::new())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *state.log.lock().unwrap(),
+        ["Report entered, from factory: true"]
+    );
+}
+
+#[tokio::test]
+async fn middleware_replaces_actions() {
+    let state = App::with_state(TestState::new(Some(ScreenID::Report)))
+        .with_screens::<TestScreens>()
+        .with_middleware(|action: &Action<ScreenID>| match action {
+            Action::Push(ScreenID::Report) => Some(Action::Push(ScreenID::Denied)),
+            action => Some(action.clone()),
+        })
+        .run_headless(Vec::new())
+        .await
+        .unwrap();
+
+    assert_eq!(*state.log.lock().unwrap(), ["Denied entered"]);
+}
+
+#[tokio::test]
+async fn error_handler_handles_hook_errors() {
+    let state = TestState::new(Some(ScreenID::Failing));
+    let log = state.log.clone();
+
+    let state = App::with_state(state)
+        .with_screens::<TestScreens>()
+        .with_error_handler(move |err, navigator| {
+            log.lock().unwrap().push(err.to_string());
+            navigator.exit();
+
+            HookErrorAction::Continue
+        })
+        .run_headless(Vec::new())
+        .await
+        .unwrap();
+
+    assert_eq!(*state.log.lock().unwrap(), ["Failing screen entered"]);
+}
+
+#[tokio::test]
+async fn global_event_handler_consumes_events() {
+    let state = TestState::new(None);
+    let log = state.log.clone();
+
+    let state = App::with_state(state)
+        .with_screens::<TestScreens>()
+        .with_global_event_handler(move |event, navigator| {
+            if event != Event::Key(KeyCode::Char('r').into()) {
+                return false;
+            }
+
+            log.lock().unwrap().push("Handler consumed r".into());
+            navigator.push(ScreenID::Report);
+
+            true
+        })
+        .run_headless(vec![
+            Event::Key(KeyCode::Char('a').into()),
+            Event::Key(KeyCode::Char('r').into()),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *state.log.lock().unwrap(),
+        [
+            "Home got an event",
+            "Handler consumed r",
+            "Report entered, from factory: false",
+        ]
+    );
+}
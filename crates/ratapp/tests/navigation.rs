@@ -0,0 +1,368 @@
+//! Checks the screens entered, paused, resumed and exited by each navigation action, running the
+//! application headlessly.
+
+use std::collections::VecDeque;
+
+use ratapp::{
+    App, DrawContext, EventPriority, HookResult, Navigator, ResumeReason, ScreenWithState, Screens,
+    StackOverflowPolicy,
+};
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode},
+};
+
+/// A navigation step, run by the current screen when a key is pressed.
+type Step = Box<dyn FnOnce(&Navigator<ScreenID>) + Send>;
+
+#[derive(Default)]
+struct TestState {
+    /// The steps left to run, one per key press.
+    steps: VecDeque<Step>,
+    /// The hooks that were called, in order.
+    log: Vec<String>,
+}
+
+/// Declares screens logging their lifecycle hooks, and running the next step on key presses.
+macro_rules! logging_screens {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Default)]
+            struct $name;
+
+            impl ScreenWithState<ScreenID, TestState> for $name {
+                fn draw_with_context(
+                    &mut self,
+                    _frame: &mut Frame,
+                    _context: DrawContext<ScreenID>,
+                    _state: &TestState,
+                ) {
+                }
+
+                async fn on_event(
+                    &mut self,
+                    _event: &Event,
+                    navigator: Navigator<ScreenID>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    if let Some(step) = state.steps.pop_front() {
+                        step(&navigator);
+                    }
+
+                    Ok(())
+                }
+
+                async fn on_enter(
+                    &mut self,
+                    _navigator: Navigator<ScreenID>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state.log.push(format!("{} entered", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_exit(
+                    &mut self,
+                    _navigator: Navigator<ScreenID>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state.log.push(format!("{} exited", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_pause(
+                    &mut self,
+                    _navigator: Navigator<ScreenID>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state.log.push(format!("{} paused", stringify!($name)));
+
+                    Ok(())
+                }
+
+                async fn on_resume(
+                    &mut self,
+                    reason: ResumeReason<ScreenID>,
+                    _navigator: Navigator<ScreenID>,
+                    state: &mut TestState,
+                ) -> HookResult {
+                    state
+                        .log
+                        .push(format!("{} resumed by {reason:?}", stringify!($name)));
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+logging_screens!(Home, First, Second, Third);
+
+#[derive(Screens)]
+enum TestScreens {
+    #[screen(default)]
+    Home(Home),
+    First(First),
+    Second(Second),
+    Third(Third),
+}
+
+/// Runs the application configured by `configure`, pressing a key for each step, and returns the
+/// hooks that were called.
+///
+/// Navigation actions are handled before the next key press, so each step runs on the screen the
+/// previous one navigated to. The screens left in the stack exit last, from the top one down.
+async fn run(
+    steps: Vec<Step>,
+    configure: impl FnOnce(App<TestState>) -> App<TestState>,
+) -> Vec<String> {
+    let events = vec![Event::Key(KeyCode::Enter.into()); steps.len()];
+    let state = TestState {
+        steps: steps.into(),
+        log: Vec::new(),
+    };
+    let app = App::with_state(state).with_event_priority(EventPriority::Actions);
+
+    configure(app)
+        .run_headless::<TestScreens>(events)
+        .await
+        .unwrap()
+        .log
+}
+
+#[tokio::test]
+async fn forward_returns_to_the_screen_went_back_from() {
+    let log = run(
+        vec![
+            Box::new(|navigator| navigator.push(ScreenID::First)),
+            Box::new(|navigator| navigator.back()),
+            Box::new(|navigator| navigator.forward()),
+        ],
+        |app| app,
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "First exited",
+            "Home resumed by Back(First)",
+            "Home paused",
+            "First entered",
+            "First exited",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn forward_without_screens_to_go_to_is_ignored() {
+    let log = run(vec![Box::new(|navigator| navigator.forward())], |app| app).await;
+
+    assert_eq!(log, ["Home entered", "Home exited"]);
+}
+
+#[tokio::test]
+async fn replace_history_enters_the_new_top_screen_only() {
+    let log = run(
+        vec![
+            Box::new(|navigator| {
+                navigator.replace_history(vec![ScreenID::Home, ScreenID::First, ScreenID::Second])
+            }),
+            Box::new(|navigator| navigator.back()),
+            Box::new(|navigator| navigator.back()),
+        ],
+        |app| app,
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home exited",
+            "Second entered",
+            "Second exited",
+            "First entered",
+            "First exited",
+            "Home entered",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn replace_root_keeps_the_screens_above_it() {
+    let log = run(
+        vec![
+            Box::new(|navigator| navigator.push(ScreenID::First)),
+            Box::new(|navigator| navigator.replace_root(ScreenID::Second)),
+            Box::new(|navigator| navigator.back()),
+        ],
+        |app| app,
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "Home exited",
+            "First exited",
+            "Second entered",
+            "Second exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn push_unique_skips_the_current_screen() {
+    let log = run(
+        vec![
+            Box::new(|navigator| {
+                // Both pushes are queued before the first one is processed.
+                navigator.push_unique(ScreenID::First);
+                navigator.push_unique(ScreenID::First);
+            }),
+            Box::new(|navigator| navigator.push_unique(ScreenID::Second)),
+        ],
+        |app| app,
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "First paused",
+            "Second entered",
+            "Second exited",
+            "First exited",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn transaction_processes_its_actions_in_order() {
+    let log = run(
+        vec![Box::new(|navigator| {
+            navigator.transaction(|transaction| {
+                transaction.push(ScreenID::First);
+                transaction.push(ScreenID::Second);
+                transaction.back();
+            })
+        })],
+        |app| app,
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "First paused",
+            "Second entered",
+            "Second exited",
+            "First resumed by Back(Second)",
+            "First exited",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn max_stack_depth_drops_the_oldest_screens_above_the_root() {
+    let log = run(
+        vec![
+            Box::new(|navigator| navigator.push(ScreenID::First)),
+            Box::new(|navigator| navigator.push(ScreenID::Second)),
+        ],
+        |app| app.with_max_stack_depth(2),
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "First paused",
+            "First exited",
+            "Second entered",
+            "Second exited",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn max_stack_depth_drops_the_newest_screens() {
+    let log = run(
+        vec![
+            Box::new(|navigator| navigator.push(ScreenID::First)),
+            Box::new(|navigator| navigator.push(ScreenID::Second)),
+        ],
+        |app| {
+            app.with_max_stack_depth(2)
+                .with_stack_overflow_policy(StackOverflowPolicy::DropNewest)
+        },
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home paused",
+            "First entered",
+            "First exited",
+            "Home exited",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn max_stack_depth_shortens_replaced_histories() {
+    let log = run(
+        vec![
+            Box::new(|navigator| {
+                navigator.replace_history(vec![
+                    ScreenID::Home,
+                    ScreenID::First,
+                    ScreenID::Second,
+                    ScreenID::Third,
+                ])
+            }),
+            Box::new(|navigator| navigator.back()),
+        ],
+        |app| app.with_max_stack_depth(3),
+    )
+    .await;
+
+    assert_eq!(
+        log,
+        [
+            "Home entered",
+            "Home exited",
+            "Third entered",
+            "Third exited",
+            "Second entered",
+            "Second exited",
+            "Home exited",
+        ]
+    );
+}
@@ -0,0 +1,142 @@
+//! Checks the lifecycle hooks called when popping back to a screen with `Navigator::pop_to()`.
+
+use std::future;
+
+use ratapp::{
+    App, DrawContext, EventSource, HookResult, Navigator, ResumeReason, ScreenWithState, Screens,
+};
+use ratatui::{Frame, Terminal, backend::TestBackend, crossterm::event::Event};
+
+/// The hooks that were called, in order.
+type Log = Vec<String>;
+
+#[derive(Screens)]
+enum TestScreens {
+    #[screen(default)]
+    First(FirstScreen),
+    Second(SecondScreen),
+    Third(ThirdScreen),
+}
+
+#[derive(Default)]
+struct FirstScreen;
+
+impl ScreenWithState<ScreenID, Log> for FirstScreen {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &Log,
+    ) {
+        frame.render_widget("First", frame.area());
+    }
+
+    async fn on_enter(&mut self, navigator: Navigator<ScreenID>, _state: &mut Log) -> HookResult {
+        navigator.push(ScreenID::Second);
+
+        Ok(())
+    }
+
+    async fn on_exit(&mut self, _navigator: Navigator<ScreenID>, state: &mut Log) -> HookResult {
+        state.push("First exited".into());
+
+        Ok(())
+    }
+
+    async fn on_resume(
+        &mut self,
+        reason: ResumeReason<ScreenID>,
+        navigator: Navigator<ScreenID>,
+        state: &mut Log,
+    ) -> HookResult {
+        match reason {
+            ResumeReason::PopTo(top) => state.push(format!("First resumed from {top:?}")),
+            reason => panic!("First was resumed for the wrong reason: {reason:?}"),
+        }
+
+        navigator.exit();
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct SecondScreen;
+
+impl ScreenWithState<ScreenID, Log> for SecondScreen {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &Log,
+    ) {
+        frame.render_widget("Second", frame.area());
+    }
+
+    async fn on_enter(&mut self, navigator: Navigator<ScreenID>, _state: &mut Log) -> HookResult {
+        navigator.push(ScreenID::Third);
+
+        Ok(())
+    }
+
+    async fn on_exit(&mut self, _navigator: Navigator<ScreenID>, state: &mut Log) -> HookResult {
+        state.push("Second exited".into());
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ThirdScreen;
+
+impl ScreenWithState<ScreenID, Log> for ThirdScreen {
+    fn draw_with_context(
+        &mut self,
+        frame: &mut Frame,
+        _context: DrawContext<ScreenID>,
+        _state: &Log,
+    ) {
+        frame.render_widget("Third", frame.area());
+    }
+
+    async fn on_enter(&mut self, navigator: Navigator<ScreenID>, _state: &mut Log) -> HookResult {
+        navigator.pop_to(ScreenID::First);
+
+        Ok(())
+    }
+
+    async fn on_exit(&mut self, _navigator: Navigator<ScreenID>, state: &mut Log) -> HookResult {
+        state.push("Third exited".into());
+
+        Ok(())
+    }
+}
+
+/// An [`EventSource`] that never produces events, so the application doesn't read the terminal.
+struct NoEvents;
+
+impl EventSource for NoEvents {
+    async fn next_event(&mut self) -> Option<Event> {
+        future::pending().await
+    }
+}
+
+#[tokio::test]
+async fn pop_to_exits_popped_screens_and_resumes_target() {
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let mut app = App::with_state(Log::new()).with_event_source(NoEvents);
+
+    app.run_with_terminal::<TestScreens, _>(&mut terminal)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        app.into_state(),
+        [
+            "Third exited",
+            "Second exited",
+            "First resumed from Third",
+            "First exited",
+        ]
+    );
+}
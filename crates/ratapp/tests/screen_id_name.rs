@@ -0,0 +1,55 @@
+//! Checks that two `#[derive(Screens)]` enums can live in the same module when their generated
+//! ID enums are given different names with `#[screen(id_name = "...")]`.
+
+use ratapp::{Screen, Screens};
+
+/// Declares screens that draw nothing, since each screen type may only appear in one variant.
+macro_rules! blank_screens {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Default)]
+            struct $name;
+
+            impl<ID> Screen<ID> for $name {}
+        )*
+    };
+}
+
+blank_screens!(HomeScreen, SettingsScreen, WelcomeScreen, FinishScreen);
+
+#[derive(Screens)]
+#[screen(id_name = "MainScreenID")]
+enum MainScreens {
+    #[screen(default)]
+    Home(HomeScreen),
+    Settings(SettingsScreen),
+}
+
+#[derive(Screens)]
+#[screen(id_name = "WizardScreenID")]
+enum WizardScreens {
+    #[screen(default)]
+    Welcome(WelcomeScreen),
+    Finish(FinishScreen),
+}
+
+#[test]
+fn id_enums_have_their_own_names() {
+    assert_eq!(
+        MainScreenID::from(&MainScreens::default()),
+        MainScreenID::Home
+    );
+    assert_eq!(
+        MainScreenID::from(&MainScreens::Settings(SettingsScreen)),
+        MainScreenID::Settings
+    );
+
+    assert_eq!(
+        WizardScreenID::from(&WizardScreens::default()),
+        WizardScreenID::Welcome
+    );
+    assert_eq!(
+        WizardScreenID::from(&WizardScreens::Finish(FinishScreen)),
+        WizardScreenID::Finish
+    );
+}
@@ -0,0 +1,91 @@
+//! Checks that the `ScreenID` enum generated by `#[derive(Screens)]` has the visibility of the
+//! screens enum, by naming it from the modules that can see the screens enum.
+
+use ratapp::Screen;
+
+#[derive(Default)]
+pub struct BlankScreen;
+
+impl<ID> Screen<ID> for BlankScreen {}
+
+mod screens {
+    pub mod public {
+        use ratapp::Screens;
+
+        use crate::BlankScreen;
+
+        #[derive(Screens)]
+        pub enum PublicScreens {
+            #[screen(default)]
+            Home(BlankScreen),
+        }
+    }
+
+    pub mod crate_visible {
+        use ratapp::Screens;
+
+        use crate::BlankScreen;
+
+        #[derive(Screens)]
+        pub(crate) enum CrateScreens {
+            #[screen(default)]
+            Home(BlankScreen),
+        }
+    }
+
+    pub mod super_visible {
+        use ratapp::Screens;
+
+        use crate::BlankScreen;
+
+        #[derive(Screens)]
+        pub(super) enum SuperScreens {
+            #[screen(default)]
+            Home(BlankScreen),
+        }
+    }
+
+    pub mod private {
+        use ratapp::Screens;
+
+        use crate::BlankScreen;
+
+        #[derive(Screens)]
+        enum PrivateScreens {
+            #[screen(default)]
+            Home(BlankScreen),
+        }
+
+        mod child {
+            use super::{PrivateScreens, ScreenID};
+
+            #[test]
+            fn private_id_is_visible_from_child_module() {
+                assert_eq!(ScreenID::from(&PrivateScreens::default()), ScreenID::Home);
+            }
+        }
+    }
+
+    #[test]
+    fn super_visible_id_is_visible_from_parent_module() {
+        use super_visible::{ScreenID, SuperScreens};
+
+        assert_eq!(ScreenID::from(&SuperScreens::default()), ScreenID::Home);
+    }
+}
+
+mod sibling {
+    use crate::screens::crate_visible::{CrateScreens, ScreenID};
+
+    #[test]
+    fn crate_visible_id_is_visible_from_sibling_module() {
+        assert_eq!(ScreenID::from(&CrateScreens::default()), ScreenID::Home);
+    }
+}
+
+pub use screens::public::{PublicScreens, ScreenID};
+
+#[test]
+fn public_id_can_be_reexported() {
+    assert_eq!(ScreenID::from(&PublicScreens::default()), ScreenID::Home);
+}